@@ -2,3 +2,6 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Scaling factor for `VaultConfig::acc_reward_per_share`
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
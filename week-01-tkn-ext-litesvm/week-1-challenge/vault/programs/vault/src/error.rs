@@ -4,4 +4,26 @@ use anchor_lang::prelude::*;
 pub enum VaultCode {
     #[msg("Insufficient balance for withdrawal")]
     InsufficientBalance,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("No deposits to distribute rewards to")]
+    NoDepositors,
+    #[msg("Reward mint must be the vault's reward mint and differ from the vault mint")]
+    InvalidRewardMint,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Deposit is locked until the lock-up timestamp")]
+    FundsLocked,
+    #[msg("Mint exceeds the minter's remaining allowance")]
+    MinterAllowanceExceeded,
+    #[msg("Mint exceeds the minter's quota for the current period")]
+    MinterQuotaExceeded,
+    #[msg("Minter period must be positive")]
+    InvalidMinterPeriod,
+    #[msg("Only the beneficiary may lock up their deposit")]
+    LockRequiresBeneficiary,
+    #[msg("Moving tokens out of the vault requires the depositor's ledger")]
+    LedgerRequired,
+    #[msg("Token account is frozen until the admin approves it")]
+    AccountNotApproved,
 }
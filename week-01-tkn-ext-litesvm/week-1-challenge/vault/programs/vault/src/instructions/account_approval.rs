@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    freeze_account, thaw_account, FreezeAccount, Mint, ThawAccount, TokenAccount, TokenInterface,
+};
+
+use crate::VaultConfig;
+
+/// KYC gate for mints created with `default_frozen`: holders start frozen
+/// and the admin thaws (approves) or re-freezes (revokes) their accounts.
+#[derive(Accounts)]
+pub struct AccountApproval<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AccountApproval<'info> {
+    pub fn approve_account(&mut self) -> Result<()> {
+        let thaw_cpi_accounts = ThawAccount {
+            account: self.token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                thaw_cpi_accounts,
+                signer_seeds,
+            );
+
+            thaw_account(cpi_ctx)
+        })
+    }
+
+    pub fn revoke_account(&mut self) -> Result<()> {
+        let freeze_cpi_accounts = FreezeAccount {
+            account: self.token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                freeze_cpi_accounts,
+                signer_seeds,
+            );
+
+            freeze_account(cpi_ctx)
+        })
+    }
+}
@@ -1,24 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{
-    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::VaultConfig;
+use crate::{error::VaultCode, utils::transfer_checked_with_hook, Amount, VaultConfig};
 
 #[derive(Accounts)]
+#[instruction(amount: u64, depositor: Option<Pubkey>)]
 pub struct AdminTransfer<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
     #[account(
-        seeds = [b"vault_config"],
+        mut,
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
         bump = vault_config.bump,
         constraint = vault_config.admin == admin.key()
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
     #[account(
-        mint::decimals = 6,
+        address = vault_config.mint,
         mint::token_program = token_program,
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -37,12 +37,43 @@ pub struct AdminTransfer<'info> {
     )]
     pub to_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Ledger of the depositor whose funds are clawed back, required when
+    /// `from_account` is the vault so `total_deposited` stays covered
+    #[account(
+        mut,
+        seeds = [
+            b"amount",
+            vault_config.key().as_ref(),
+            depositor.unwrap_or_default().as_ref()
+        ],
+        bump = ledger.bump,
+    )]
+    pub ledger: Option<Account<'info, Amount>>,
+
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> AdminTransfer<'info> {
-    pub fn admin_transfer(&mut self, amount: u64) -> Result<()> {
-       
+    /// `depositor` selects the `ledger` PDA and is only needed when clawing back from the vault.
+    /// `remaining_accounts` are the extra accounts the mint's transfer hook resolves.
+    pub fn admin_transfer(
+        &mut self,
+        amount: u64,
+        _depositor: Option<Pubkey>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if self.from_account.key() == self.vault_config.vault {
+            let acc_reward_per_share = self.vault_config.acc_reward_per_share;
+            let ledger = self.ledger.as_mut().ok_or(VaultCode::LedgerRequired)?;
+            ledger.debit(amount, acc_reward_per_share)?;
+
+            self.vault_config.total_deposited = self
+                .vault_config
+                .total_deposited
+                .checked_sub(amount)
+                .ok_or(VaultCode::MathOverflow)?;
+        }
+
         // Admin acts as permanent delegate - can transfer without owner signature
         let transfer_cpi_program = self.token_program.to_account_info();
 
@@ -53,9 +84,10 @@ impl<'info> AdminTransfer<'info> {
             authority: self.admin.to_account_info(), // Admin is permanent delegate
         };
 
-        let cpi_ctx = CpiContext::new(transfer_cpi_program, transfer_cpi_accounts);
+        let cpi_ctx = CpiContext::new(transfer_cpi_program, transfer_cpi_accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        transfer_checked_with_hook(cpi_ctx, amount, self.mint.decimals)?;
 
         Ok(())
     }
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::VaultCode, Amount, VaultConfig};
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"amount", vault_config.key().as_ref(), user.key().as_ref()],
+        bump = amount_pda.bump,
+    )]
+    pub amount_pda: Account<'info, Amount>,
+
+    #[account(
+        address = vault_config.reward_mint @ VaultCode::InvalidRewardMint,
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = vault_config,
+        associated_token::token_program = reward_token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user,
+        associated_token::token_program = reward_token_program
+    )]
+    pub user_reward_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    pub fn claim_rewards(&mut self) -> Result<()> {
+        let acc_reward_per_share = self.vault_config.acc_reward_per_share;
+
+        self.amount_pda.settle_rewards(acc_reward_per_share)?;
+        self.amount_pda.sync_reward_debt(acc_reward_per_share)?;
+
+        let rewards = self.amount_pda.pending_rewards;
+        require!(rewards > 0, VaultCode::NoRewardsToClaim);
+        self.amount_pda.pending_rewards = 0;
+
+        let transfer_cpi_accounts = TransferChecked {
+            from: self.reward_vault.to_account_info(),
+            to: self.user_reward_ata.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.reward_token_program.to_account_info(),
+                transfer_cpi_accounts,
+                signer_seeds,
+            );
+
+            transfer_checked(cpi_ctx, rewards, self.reward_mint.decimals)
+        })
+    }
+}
@@ -1,34 +1,39 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022::{transfer_checked, TransferChecked},
+    token_2022::TransferChecked,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::{Amount, VaultConfig};
+use crate::{
+    error::VaultCode,
+    utils::{transfer_checked_with_hook, transfer_fee_for},
+    Amount, VaultConfig,
+};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
     #[account(
         init_if_needed,
         payer = user,
-        seeds = [b"amount", user.key().as_ref()],
+        seeds = [b"amount", vault_config.key().as_ref(), user.key().as_ref()],
         bump,
         space = 8 + Amount::INIT_SPACE
     )]
     pub amount_pda: Account<'info, Amount>,
 
     #[account(
-        seeds = [b"vault_config"], 
-        bump = vault_config.bump
-    )]
-    pub vault_config: Account<'info, VaultConfig>,
-
-    #[account(
-        mint::decimals = 6,
+        address = vault_config.mint,
         mint::token_program = token_program,
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -52,11 +57,26 @@ pub struct Deposit<'info> {
 }
 
 impl<'info> Deposit<'info> {
-    pub fn deposit(&mut self, amount: u64, bumps: &DepositBumps) -> Result<()> {
-        self.amount_pda.set_inner(Amount {
-            amount: self.amount_pda.amount + amount,
-            bump: bumps.amount_pda,
-        });
+    /// `remaining_accounts` are the extra accounts the mint's transfer hook resolves
+    pub fn deposit(
+        &mut self,
+        amount: u64,
+        bumps: &DepositBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // With a transfer fee the vault only receives the net amount, so only credit that
+        let fee = transfer_fee_for(&self.mint.to_account_info(), amount)?;
+        let net_amount = amount - fee;
+
+        self.amount_pda.bump = bumps.amount_pda;
+        self.amount_pda
+            .credit(net_amount, self.vault_config.acc_reward_per_share)?;
+
+        self.vault_config.total_deposited = self
+            .vault_config
+            .total_deposited
+            .checked_add(net_amount)
+            .ok_or(VaultCode::MathOverflow)?;
 
         let transfer_cpi_program = self.token_program.to_account_info();
 
@@ -67,10 +87,11 @@ impl<'info> Deposit<'info> {
             authority: self.user.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(transfer_cpi_program, transfer_cpi_accounts);
+        let cpi_ctx = CpiContext::new(transfer_cpi_program, transfer_cpi_accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, amount, self.mint.decimals)?;
 
-        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
-        
         Ok(())
     }
 }
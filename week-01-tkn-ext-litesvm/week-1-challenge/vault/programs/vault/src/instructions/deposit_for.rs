@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::TransferChecked,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::VaultCode,
+    utils::{transfer_checked_with_hook, transfer_fee_for},
+    Amount, VaultConfig,
+};
+
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Must also sign when `lock_until` is set
+    pub beneficiary: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"amount", vault_config.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+        space = 8 + Amount::INIT_SPACE
+    )]
+    pub amount_pda: Account<'info, Amount>,
+
+    #[account(
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payer
+    )]
+    pub payer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault_config
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositFor<'info> {
+    /// Funds the vault from the payer's ATA and credits the beneficiary's ledger.
+    /// `lock_until` locks the beneficiary's whole balance, so it needs their signature,
+    /// and can only extend an existing lock-up, never shorten it.
+    pub fn deposit_for(
+        &mut self,
+        amount: u64,
+        lock_until: Option<i64>,
+        bumps: &DepositForBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let fee = transfer_fee_for(&self.mint.to_account_info(), amount)?;
+        let net_amount = amount - fee;
+
+        self.amount_pda.bump = bumps.amount_pda;
+        self.amount_pda
+            .credit(net_amount, self.vault_config.acc_reward_per_share)?;
+
+        if let Some(lock_until) = lock_until {
+            require!(
+                self.beneficiary.is_signer,
+                VaultCode::LockRequiresBeneficiary
+            );
+            self.amount_pda.lock_until = self.amount_pda.lock_until.max(lock_until);
+        }
+
+        self.vault_config.total_deposited = self
+            .vault_config
+            .total_deposited
+            .checked_add(net_amount)
+            .ok_or(VaultCode::MathOverflow)?;
+
+        let transfer_cpi_program = self.token_program.to_account_info();
+
+        let transfer_cpi_accounts = TransferChecked {
+            from: self.payer_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.payer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(transfer_cpi_program, transfer_cpi_accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, amount, self.mint.decimals)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::VaultCode, VaultConfig};
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key(),
+        constraint = vault_config.reward_mint == Pubkey::default()
+            || vault_config.reward_mint == reward_mint.key() @ VaultCode::InvalidRewardMint
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        constraint = reward_mint.key() != vault_config.mint @ VaultCode::InvalidRewardMint,
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = admin,
+        token::token_program = reward_token_program,
+    )]
+    pub admin_reward_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = reward_mint,
+        associated_token::authority = vault_config,
+        associated_token::token_program = reward_token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundRewards<'info> {
+    pub fn fund_rewards(&mut self, amount: u64) -> Result<()> {
+        // Fail before taking the admin's tokens rather than strand them in the reward vault
+        require!(self.vault_config.total_deposited > 0, VaultCode::NoDepositors);
+
+        // The first funding picks the reward mint for the lifetime of the vault
+        self.vault_config.reward_mint = self.reward_mint.key();
+
+        let balance_before = self.reward_vault.amount;
+
+        let transfer_cpi_accounts = TransferChecked {
+            from: self.admin_reward_ata.to_account_info(),
+            to: self.reward_vault.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            authority: self.admin.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.reward_token_program.to_account_info(), transfer_cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)?;
+
+        // Only distribute what actually arrived, in case the reward mint charges a fee
+        self.reward_vault.reload()?;
+        let received = self.reward_vault.amount - balance_before;
+
+        self.vault_config.distribute_rewards(received)
+    }
+}
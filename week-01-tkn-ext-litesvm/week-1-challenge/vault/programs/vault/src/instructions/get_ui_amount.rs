@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{utils::ui_amount_for, Amount, VaultConfig};
+
+#[derive(Accounts)]
+pub struct GetUiAmount<'info> {
+    /// CHECK: only used to derive the ledger PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [b"amount", vault_config.key().as_ref(), user.key().as_ref()],
+        bump = amount_pda.bump,
+    )]
+    pub amount_pda: Account<'info, Amount>,
+
+    #[account(
+        address = vault_config.mint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+impl<'info> GetUiAmount<'info> {
+    /// Read-only: meant to be simulated, the UI amount comes back as return data
+    pub fn get_ui_amount(&self) -> Result<String> {
+        let ui_amount = ui_amount_for(&self.mint.to_account_info(), self.amount_pda.amount)?;
+
+        msg!("UI amount: {}", ui_amount);
+
+        Ok(ui_amount)
+    }
+}
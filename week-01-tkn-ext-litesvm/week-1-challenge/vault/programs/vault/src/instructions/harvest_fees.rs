@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022_extensions::{harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint},
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::VaultConfig;
+
+#[derive(Accounts)]
+pub struct HarvestFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> HarvestFees<'info> {
+    /// Moves withheld fees from the token accounts passed as remaining accounts into the mint
+    pub fn harvest_fees(&mut self, sources: &[AccountInfo<'info>]) -> Result<()> {
+        let harvest_cpi_accounts = HarvestWithheldTokensToMint {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), harvest_cpi_accounts);
+
+        harvest_withheld_tokens_to_mint(cpi_ctx, sources.to_vec())
+    }
+}
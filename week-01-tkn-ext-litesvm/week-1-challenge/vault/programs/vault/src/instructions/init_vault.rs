@@ -1,12 +1,53 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    associated_token::{create, AssociatedToken, Create},
+    token_2022::{
+        initialize_mint2,
+        spl_token_2022::{
+            extension::ExtensionType,
+            state::{AccountState, Mint as MintState},
+        },
+        thaw_account, InitializeMint2, ThawAccount, Token2022,
+    },
+    token_2022_extensions::{
+        default_account_state_initialize, interest_bearing_mint_initialize,
+        metadata_pointer_initialize, permanent_delegate_initialize, token_metadata_initialize,
+        transfer_fee_initialize, transfer_hook_initialize, DefaultAccountStateInitialize,
+        InterestBearingMintInitialize, MetadataPointerInitialize, PermanentDelegateInitialize,
+        TokenMetadataInitialize, TransferFeeInitialize, TransferHookInitialize,
+    },
 };
 
-use crate::VaultConfig;
+use crate::{utils::update_account_lamports_to_minimum_balance, VaultConfig};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitVaultArgs {
+    pub vault_id: u64,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Enables the TransferFeeConfig extension, with the vault config PDA as fee authority
+    pub transfer_fee: Option<TransferFeeArgs>,
+    /// New token accounts start frozen until the admin approves them,
+    /// with the vault config PDA as freeze authority
+    pub default_frozen: bool,
+    /// Enables the InterestBearingConfig extension at this rate (basis points per year),
+    /// with the vault config PDA as rate authority
+    pub interest_rate: Option<i16>,
+}
 
 #[derive(Accounts)]
+#[instruction(args: InitVaultArgs)]
 pub struct InitVault<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -15,49 +56,243 @@ pub struct InitVault<'info> {
         init,
         payer = admin,
         space = 8 + VaultConfig::INIT_SPACE,
-        seeds = [b"vault_config"],
+        seeds = [b"vault_config", args.vault_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
-    #[account(
-        init,
-        payer = admin,
-        mint::authority = admin,
-        mint::decimals = 6,
-        mint::token_program = token_program,
-        extensions::transfer_hook::authority = admin,
-        extensions::transfer_hook::program_id = transfer_hook_program,
-        extensions::permanent_delegate::delegate = admin,
-    )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    /// Created in the handler, since the extension set depends on the instruction args
+    #[account(mut)]
+    pub mint: Signer<'info>,
 
     /// CHECK: this will be the separate program created for the whitelist transfer hook
     pub transfer_hook_program: UncheckedAccount<'info>,
 
-    #[account(
-        init, 
-        payer = admin, 
-        associated_token::mint = mint, 
-        associated_token::authority = vault_config,
-        associated_token::token_program = token_program
-    )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: vault ATA, created and validated by the associated token program
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitVault<'info> {
-    pub fn init_vault(&mut self, bumps: &InitVaultBumps) -> Result<()> {
-        self.vault_config.set_inner(VaultConfig { 
-            admin: self.admin.key(), 
-            vault: self.vault.key(), 
+    pub fn init_vault(&mut self, args: InitVaultArgs, bumps: &InitVaultBumps) -> Result<()> {
+        self.vault_config.set_inner(VaultConfig {
+            admin: self.admin.key(),
+            vault: self.vault.key(),
             mint: self.mint.key(),
-            bump:  bumps.vault_config
+            bump: bumps.vault_config,
+            vault_id: args.vault_id,
+            total_deposited: 0,
+            reward_mint: Pubkey::default(),
+            acc_reward_per_share: 0,
         });
-        
+
+        let default_frozen = args.default_frozen;
+
+        self.create_mint(&args)?;
+        self.init_metadata(args)?;
+        self.create_vault()?;
+
+        if default_frozen {
+            self.thaw_vault()?;
+        }
+
+        Ok(())
+    }
+
+    fn create_mint(&mut self, args: &InitVaultArgs) -> Result<()> {
+        let mut extensions = vec![
+            ExtensionType::TransferHook,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::MetadataPointer,
+        ];
+        if args.transfer_fee.is_some() {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        if args.default_frozen {
+            extensions.push(ExtensionType::DefaultAccountState);
+        }
+        if args.interest_rate.is_some() {
+            extensions.push(ExtensionType::InterestBearingConfig);
+        }
+
+        // Only the fixed-size extensions are allocated here, TokenMetadata reallocs later
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.admin.to_account_info(),
+                    to: self.mint.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &self.token_program.key(),
+        )?;
+
+        // Extensions must be initialized before the mint itself
+        transfer_hook_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferHookInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            Some(self.admin.key()),
+            Some(self.transfer_hook_program.key()),
+        )?;
+
+        permanent_delegate_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                PermanentDelegateInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            &self.admin.key(),
+        )?;
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            Some(self.vault_config.key()),
+            Some(self.mint.key()),
+        )?;
+
+        if let Some(transfer_fee) = &args.transfer_fee {
+            transfer_fee_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferFeeInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                Some(&self.vault_config.key()),
+                Some(&self.vault_config.key()),
+                transfer_fee.basis_points,
+                transfer_fee.maximum_fee,
+            )?;
+        }
+
+        if args.default_frozen {
+            default_account_state_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    DefaultAccountStateInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                &AccountState::Frozen,
+            )?;
+        }
+
+        if let Some(interest_rate) = args.interest_rate {
+            interest_bearing_mint_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    InterestBearingMintInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                Some(self.vault_config.key()),
+                interest_rate,
+            )?;
+        }
+
+        let freeze_authority = args.default_frozen.then(|| self.vault_config.key());
+
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            args.decimals,
+            &self.vault_config.key(),
+            freeze_authority.as_ref(),
+        )?;
+
         Ok(())
     }
+
+    fn init_metadata(&mut self, args: InitVaultArgs) -> Result<()> {
+        // Metadata lives on the mint itself; the vault config PDA is the update authority
+        let metadata_cpi_accounts = TokenMetadataInitialize {
+            program_id: self.token_program.to_account_info(),
+            metadata: self.mint.to_account_info(),
+            update_authority: self.vault_config.to_account_info(),
+            mint_authority: self.vault_config.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                metadata_cpi_accounts,
+                signer_seeds,
+            );
+
+            token_metadata_initialize(cpi_ctx, args.name, args.symbol, args.uri)
+        })?;
+
+        // The metadata write reallocs the mint, so cover the extra rent
+        update_account_lamports_to_minimum_balance(
+            self.mint.to_account_info(),
+            self.admin.to_account_info(),
+            self.system_program.to_account_info(),
+        )
+    }
+
+    fn create_vault(&mut self) -> Result<()> {
+        let create_cpi_accounts = Create {
+            payer: self.admin.to_account_info(),
+            associated_token: self.vault.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+            mint: self.mint.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            create_cpi_accounts,
+        );
+
+        create(cpi_ctx)
+    }
+
+    /// The vault ATA is created frozen under DefaultAccountState, so approve it right away
+    fn thaw_vault(&mut self) -> Result<()> {
+        let thaw_cpi_accounts = ThawAccount {
+            account: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                thaw_cpi_accounts,
+                signer_seeds,
+            );
+
+            thaw_account(cpi_ctx)
+        })
+    }
 }
@@ -5,23 +5,38 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
+use crate::{error::VaultCode, Minter, VaultConfig};
+
 #[derive(Accounts)]
 pub struct MintToken<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 
     pub user: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"minter", vault_config.key().as_ref(), authority.key().as_ref()],
+        bump = minter.bump,
+    )]
+    pub minter: Account<'info, Minter>,
+
     #[account(
         mut,
-        mint::decimals = 6,
+        address = vault_config.mint,
         mint::token_program = token_program
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
-        payer = admin,
+        payer = authority,
         associated_token::mint = mint,
         associated_token::authority = user,
         associated_token::token_program = token_program
@@ -34,18 +49,31 @@ pub struct MintToken<'info> {
 }
 
 impl<'info> MintToken<'info> {
+    /// On a `default_frozen` mint a new holder's ATA is created frozen, so minting to it
+    /// fails until the admin approves the account. Minters can't skip that KYC step.
     pub fn mint_token(&mut self, amount: u64) -> Result<()> {
+        require!(!self.user_ata.is_frozen(), VaultCode::AccountNotApproved);
+
+        self.minter.consume_quota(amount, Clock::get()?.unix_timestamp)?;
+
         let mint_token_cpi_program = self.token_program.to_account_info();
 
+        // The vault config PDA holds mint authority, minters only sign for their quota
         let mint_token_cpi_accounts = MintTo {
             to: self.user_ata.to_account_info(),
             mint: self.mint.to_account_info(),
-            authority: self.admin.to_account_info(),
+            authority: self.vault_config.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(mint_token_cpi_program, mint_token_cpi_accounts);
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                mint_token_cpi_program,
+                mint_token_cpi_accounts,
+                signer_seeds,
+            );
 
-        mint_to(cpi_ctx, amount)?;
+            mint_to(cpi_ctx, amount)
+        })?;
 
         Ok(())
     }
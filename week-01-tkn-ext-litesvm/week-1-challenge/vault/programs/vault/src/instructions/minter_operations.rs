@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::VaultCode, Minter, VaultConfig};
+
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct GrantMinter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Minter::INIT_SPACE,
+        seeds = [b"minter", vault_config.key().as_ref(), authority.as_ref()],
+        bump,
+    )]
+    pub minter: Account<'info, Minter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct RevokeMinter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"minter", vault_config.key().as_ref(), authority.as_ref()],
+        bump = minter.bump,
+    )]
+    pub minter: Account<'info, Minter>,
+}
+
+impl<'info> GrantMinter<'info> {
+    /// Creates the minter, or updates its limits while keeping what it already minted
+    pub fn grant_minter(
+        &mut self,
+        authority: Pubkey,
+        allowance: u64,
+        period_quota: u64,
+        period_seconds: i64,
+        bumps: &GrantMinterBumps,
+    ) -> Result<()> {
+        require!(period_seconds > 0, VaultCode::InvalidMinterPeriod);
+
+        self.minter.set_inner(Minter {
+            vault_config: self.vault_config.key(),
+            authority,
+            allowance,
+            period_quota,
+            period_seconds,
+            period_start: self.minter.period_start,
+            minted_in_period: self.minter.minted_in_period,
+            total_minted: self.minter.total_minted,
+            bump: bumps.minter,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RevokeMinter<'info> {
+    pub fn revoke_minter(&mut self, _authority: Pubkey) -> Result<()> {
+        // Closed via `close = admin`
+        Ok(())
+    }
+}
@@ -1,11 +1,35 @@
+pub mod account_approval;
 pub mod deposit;
+pub mod deposit_for;
 pub mod admin_transfer;
+pub mod claim_rewards;
+pub mod fund_rewards;
+pub mod get_ui_amount;
+pub mod harvest_fees;
 pub mod init_vault;
 pub mod mint_tkn;
+pub mod minter_operations;
+pub mod reconcile;
+pub mod set_rate;
+pub mod set_transfer_fee;
+pub mod update_metadata;
 pub mod withdraw;
+pub mod withdraw_fees;
 
+pub use account_approval::*;
 pub use deposit::*;
+pub use deposit_for::*;
 pub use admin_transfer::*;
+pub use claim_rewards::*;
+pub use fund_rewards::*;
+pub use get_ui_amount::*;
+pub use harvest_fees::*;
 pub use init_vault::*;
 pub use mint_tkn::*;
+pub use minter_operations::*;
+pub use reconcile::*;
+pub use set_rate::*;
+pub use set_transfer_fee::*;
+pub use update_metadata::*;
 pub use withdraw::*;
+pub use withdraw_fees::*;
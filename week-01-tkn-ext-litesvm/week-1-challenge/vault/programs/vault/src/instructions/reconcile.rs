@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{ReconciliationReport, VaultConfig};
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        address = vault_config.vault,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReconciliationReport::INIT_SPACE,
+        seeds = [b"reconciliation", vault_config.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, ReconciliationReport>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Reconcile<'info> {
+    /// Permissionless: anyone can snapshot ledger coverage into the report
+    pub fn reconcile(&mut self, bumps: &ReconcileBumps) -> Result<()> {
+        let clock = Clock::get()?;
+        let vault_balance = self.vault.amount;
+        let total_deposited = self.vault_config.total_deposited;
+
+        self.report.set_inner(ReconciliationReport {
+            vault_config: self.vault_config.key(),
+            vault_balance,
+            total_deposited,
+            surplus: vault_balance.saturating_sub(total_deposited),
+            deficit: total_deposited.saturating_sub(vault_balance),
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            bump: bumps.report,
+        });
+
+        msg!(
+            "Reconciled: vault holds {}, ledgers owe {}",
+            vault_balance,
+            total_deposited
+        );
+
+        Ok(())
+    }
+}
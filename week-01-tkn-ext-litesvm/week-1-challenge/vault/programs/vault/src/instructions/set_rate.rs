@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022_extensions::{interest_bearing_mint_update_rate, InterestBearingMintUpdateRate},
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::VaultConfig;
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SetRate<'info> {
+    /// Interest already accrued is kept; the new rate applies from now on
+    pub fn set_rate(&mut self, rate: i16) -> Result<()> {
+        let update_rate_cpi_accounts = InterestBearingMintUpdateRate {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.mint.to_account_info(),
+            rate_authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                update_rate_cpi_accounts,
+                signer_seeds,
+            );
+
+            interest_bearing_mint_update_rate(cpi_ctx, rate)
+        })
+    }
+}
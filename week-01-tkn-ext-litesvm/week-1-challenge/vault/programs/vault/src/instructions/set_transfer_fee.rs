@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022_extensions::{transfer_fee_set, TransferFeeSetTransferFee},
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::VaultConfig;
+
+#[derive(Accounts)]
+pub struct SetTransferFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SetTransferFee<'info> {
+    /// The new schedule takes effect two epochs later, as enforced by Token-2022
+    pub fn set_transfer_fee(&mut self, basis_points: u16, maximum_fee: u64) -> Result<()> {
+        let set_fee_cpi_accounts = TransferFeeSetTransferFee {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                set_fee_cpi_accounts,
+                signer_seeds,
+            );
+
+            transfer_fee_set(cpi_ctx, basis_points, maximum_fee)
+        })
+    }
+}
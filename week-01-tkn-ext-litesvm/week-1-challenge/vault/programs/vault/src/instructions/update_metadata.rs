@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022_extensions::{
+        spl_token_metadata_interface::state::Field, token_metadata_update_field,
+        TokenMetadataUpdateField,
+    },
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::{utils::update_account_lamports_to_minimum_balance, VaultConfig};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateMetadata<'info> {
+    pub fn update_metadata(&mut self, field: MetadataField, value: String) -> Result<()> {
+        let update_cpi_accounts = TokenMetadataUpdateField {
+            program_id: self.token_program.to_account_info(),
+            metadata: self.mint.to_account_info(),
+            update_authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                update_cpi_accounts,
+                signer_seeds,
+            );
+
+            token_metadata_update_field(cpi_ctx, field.into(), value)
+        })?;
+
+        // A longer value grows the mint account, so the admin covers the extra rent
+        update_account_lamports_to_minimum_balance(
+            self.mint.to_account_info(),
+            self.admin.to_account_info(),
+            self.system_program.to_account_info(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022::{transfer_checked, TransferChecked},
+    token_2022::TransferChecked,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::{error::VaultCode, Amount, VaultConfig};
+use crate::{error::VaultCode, utils::transfer_checked_with_hook, Amount, VaultConfig};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
@@ -14,20 +14,20 @@ pub struct Withdraw<'info> {
 
     #[account(
         mut,
-        close = user,
-        seeds = [b"amount", user.key().as_ref()],
-        bump = amount_pda.bump,
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump
     )]
-    pub amount_pda: Account<'info, Amount>,
+    pub vault_config: Account<'info, VaultConfig>,
 
     #[account(
-        seeds = [b"vault_config"], 
-        bump = vault_config.bump
+        mut,
+        seeds = [b"amount", vault_config.key().as_ref(), user.key().as_ref()],
+        bump = amount_pda.bump,
     )]
-    pub vault_config: Account<'info, VaultConfig>,
+    pub amount_pda: Account<'info, Amount>,
 
     #[account(
-        mint::decimals = 6,
+        address = vault_config.mint,
         mint::token_program = token_program,
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -52,8 +52,14 @@ pub struct Withdraw<'info> {
 }
 
 impl<'info> Withdraw<'info> {
-    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+    /// The full amount leaves the vault, any transfer fee is withheld in the user's ATA.
+    /// `remaining_accounts` are the extra accounts the mint's transfer hook resolves.
+    pub fn withdraw(&mut self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
         require!(self.amount_pda.amount >= amount,VaultCode::InsufficientBalance);
+        require!(
+            Clock::get()?.unix_timestamp >= self.amount_pda.lock_until,
+            VaultCode::FundsLocked
+        );
 
         let withdraw_cpi_program = self.token_program.to_account_info();
 
@@ -64,22 +70,31 @@ impl<'info> Withdraw<'info> {
             authority: self.vault_config.to_account_info(),
         };
 
-        let seeds: &[&[u8]] = &[
-            b"vault_config", 
-            &[self.vault_config.bump]
-        ];
-        let signer_seeds = &[seeds];
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                withdraw_cpi_program,
+                withdraw_cpi_accounts,
+                signer_seeds,
+            )
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            withdraw_cpi_program, 
-            withdraw_cpi_accounts, 
-            signer_seeds
-        );
+            transfer_checked_with_hook(cpi_ctx, amount, self.mint.decimals)
+        })?;
+
+        self.amount_pda
+            .debit(amount, self.vault_config.acc_reward_per_share)?;
+
+        self.vault_config.total_deposited = self
+            .vault_config
+            .total_deposited
+            .checked_sub(amount)
+            .ok_or(VaultCode::MathOverflow)?;
 
-        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        // Keep the ledger around while it still holds unclaimed rewards
+        if self.amount_pda.amount == 0 && self.amount_pda.pending_rewards == 0 {
+            self.amount_pda.close(self.user.to_account_info())?;
+        }
 
-        self.amount_pda.amount -= amount;
-        
         Ok(())
     }
 }
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022_extensions::{withdraw_withheld_tokens_from_mint, WithdrawWithheldTokensFromMint},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::VaultConfig;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_config", vault_config.vault_id.to_le_bytes().as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.admin == admin.key()
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        address = vault_config.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawFees<'info> {
+    /// Sends the fees harvested into the mint to the treasury token account
+    pub fn withdraw_fees(&mut self) -> Result<()> {
+        let withdraw_cpi_accounts = WithdrawWithheldTokensFromMint {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.mint.to_account_info(),
+            destination: self.treasury.to_account_info(),
+            authority: self.vault_config.to_account_info(),
+        };
+
+        self.vault_config.with_signer(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                withdraw_cpi_accounts,
+                signer_seeds,
+            );
+
+            withdraw_withheld_tokens_from_mint(cpi_ctx)
+        })
+    }
+}
@@ -3,7 +3,9 @@ pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
+#[allow(clippy::module_inception)]
 pub mod tests;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -17,19 +19,92 @@ declare_id!("3NiEScNK9VCHsUKbaGQVJTef5iPTQuRt4jBkWxkDXMfu");
 pub mod vault {
     use super::*;
 
-    pub fn initialize_vault(ctx: Context<InitVault>) -> Result<()> {
-        ctx.accounts.init_vault(&ctx.bumps)
+    pub fn initialize_vault(ctx: Context<InitVault>, args: InitVaultArgs) -> Result<()> {
+        ctx.accounts.init_vault(args, &ctx.bumps)
     }
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        ctx.accounts.deposit(amount, &ctx.bumps)
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.deposit(amount, &ctx.bumps, ctx.remaining_accounts)
     }
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw(amount)
+    pub fn deposit_for<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositFor<'info>>,
+        amount: u64,
+        lock_until: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .deposit_for(amount, lock_until, &ctx.bumps, ctx.remaining_accounts)
+    }
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw(amount, ctx.remaining_accounts)
     }
     pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
         ctx.accounts.mint_token(amount)
     }
-    pub fn admin_transfer(ctx: Context<AdminTransfer>, amount: u64) -> Result<()> {
-        ctx.accounts.admin_transfer(amount)
+    pub fn admin_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdminTransfer<'info>>,
+        amount: u64,
+        depositor: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .admin_transfer(amount, depositor, ctx.remaining_accounts)
+    }
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        ctx.accounts.update_metadata(field, value)
+    }
+    pub fn set_transfer_fee(
+        ctx: Context<SetTransferFee>,
+        basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_transfer_fee(basis_points, maximum_fee)
+    }
+    pub fn harvest_fees<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestFees<'info>>) -> Result<()> {
+        ctx.accounts.harvest_fees(ctx.remaining_accounts)
+    }
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        ctx.accounts.withdraw_fees()
+    }
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rewards(amount)
+    }
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        ctx.accounts.claim_rewards()
+    }
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<()> {
+        ctx.accounts.reconcile(&ctx.bumps)
+    }
+    pub fn approve_account(ctx: Context<AccountApproval>) -> Result<()> {
+        ctx.accounts.approve_account()
+    }
+    pub fn revoke_account(ctx: Context<AccountApproval>) -> Result<()> {
+        ctx.accounts.revoke_account()
+    }
+    pub fn grant_minter(
+        ctx: Context<GrantMinter>,
+        authority: Pubkey,
+        allowance: u64,
+        period_quota: u64,
+        period_seconds: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .grant_minter(authority, allowance, period_quota, period_seconds, &ctx.bumps)
+    }
+    pub fn revoke_minter(ctx: Context<RevokeMinter>, authority: Pubkey) -> Result<()> {
+        ctx.accounts.revoke_minter(authority)
+    }
+    pub fn set_rate(ctx: Context<SetRate>, rate: i16) -> Result<()> {
+        ctx.accounts.set_rate(rate)
+    }
+    pub fn get_ui_amount(ctx: Context<GetUiAmount>) -> Result<String> {
+        ctx.accounts.get_ui_amount()
     }
 }
@@ -1,8 +1,64 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::VaultCode, REWARD_PRECISION};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Amount {
     pub amount: u64,
     pub bump: u8,
+    /// Unix timestamp before which withdrawals are rejected, set by `deposit_for`
+    pub lock_until: i64,
+    /// `amount * acc_reward_per_share` at the last settlement
+    pub reward_debt: u128,
+    /// Settled rewards not yet claimed
+    pub pending_rewards: u64,
+}
+
+impl Amount {
+    fn accrued_rewards(&self, acc_reward_per_share: u128) -> Result<u128> {
+        Ok((self.amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(VaultCode::MathOverflow)?
+            / REWARD_PRECISION)
+    }
+
+    /// Moves rewards earned since the last settlement into `pending_rewards`.
+    /// Must run before `amount` changes, followed by `sync_reward_debt`.
+    pub fn settle_rewards(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let earned = self
+            .accrued_rewards(acc_reward_per_share)?
+            .saturating_sub(self.reward_debt);
+
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(u64::try_from(earned).map_err(|_| VaultCode::MathOverflow)?)
+            .ok_or(VaultCode::MathOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn sync_reward_debt(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accrued_rewards(acc_reward_per_share)?;
+        Ok(())
+    }
+
+    /// Settles rewards at the old balance, then credits `amount`
+    pub fn credit(&mut self, amount: u64, acc_reward_per_share: u128) -> Result<()> {
+        self.settle_rewards(acc_reward_per_share)?;
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(VaultCode::MathOverflow)?;
+        self.sync_reward_debt(acc_reward_per_share)
+    }
+
+    /// Settles rewards at the old balance, then debits `amount`
+    pub fn debit(&mut self, amount: u64, acc_reward_per_share: u128) -> Result<()> {
+        require!(self.amount >= amount, VaultCode::InsufficientBalance);
+
+        self.settle_rewards(acc_reward_per_share)?;
+        self.amount -= amount;
+        self.sync_reward_debt(acc_reward_per_share)
+    }
 }
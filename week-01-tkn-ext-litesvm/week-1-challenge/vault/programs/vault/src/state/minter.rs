@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultCode;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Minter {
+    pub vault_config: Pubkey,
+    pub authority: Pubkey,
+    /// Lifetime cap, `total_minted` can never exceed it
+    pub allowance: u64,
+    /// Max tokens per period of `period_seconds`
+    pub period_quota: u64,
+    pub period_seconds: i64,
+    pub period_start: i64,
+    pub minted_in_period: u64,
+    pub total_minted: u64,
+    pub bump: u8,
+}
+
+impl Minter {
+    /// Checks `amount` against both the allowance and the current period's quota,
+    /// starting a fresh period once the previous one has elapsed.
+    pub fn consume_quota(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now >= self.period_start.saturating_add(self.period_seconds) {
+            self.period_start = now;
+            self.minted_in_period = 0;
+        }
+
+        let total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .ok_or(VaultCode::MathOverflow)?;
+        require!(
+            total_minted <= self.allowance,
+            VaultCode::MinterAllowanceExceeded
+        );
+
+        let minted_in_period = self
+            .minted_in_period
+            .checked_add(amount)
+            .ok_or(VaultCode::MathOverflow)?;
+        require!(
+            minted_in_period <= self.period_quota,
+            VaultCode::MinterQuotaExceeded
+        );
+
+        self.total_minted = total_minted;
+        self.minted_in_period = minted_in_period;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
 pub mod amount;
+pub mod minter;
+pub mod reconciliation_report;
 pub mod vault_config;
 
 pub use amount::*;
+pub use minter::*;
+pub use reconciliation_report::*;
 pub use vault_config::*;
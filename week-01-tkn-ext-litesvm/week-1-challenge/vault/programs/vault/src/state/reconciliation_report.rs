@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReconciliationReport {
+    pub vault_config: Pubkey,
+    /// Tokens actually held by the vault ATA
+    pub vault_balance: u64,
+    /// Sum of all `Amount` ledgers at the time of the check
+    pub total_deposited: u64,
+    /// Tokens held beyond the ledgers, e.g. direct donations
+    pub surplus: u64,
+    /// Ledger balances not covered by tokens, e.g. permanent-delegate moves
+    pub deficit: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::VaultCode, REWARD_PRECISION};
+
 #[account]
 #[derive(InitSpace)]
 pub struct VaultConfig {
@@ -7,4 +9,39 @@ pub struct VaultConfig {
     pub vault: Pubkey,
     pub mint: Pubkey,
     pub bump: u8,
+    /// Seed that lets one deployment host many independent vaults
+    pub vault_id: u64,
+    /// Sum of all `Amount` ledgers. `admin_transfer` out of the vault debits them,
+    /// permanent-delegate transfers made directly against Token-2022 bypass the
+    /// ledgers and show up as a deficit in `reconcile`.
+    pub total_deposited: u64,
+    /// Set by the first `fund_rewards` call
+    pub reward_mint: Pubkey,
+    /// Rewards per deposited token, scaled by `REWARD_PRECISION`
+    pub acc_reward_per_share: u128,
+}
+
+impl VaultConfig {
+    /// Runs `f` with the signer seeds of this PDA, for CPIs it authorizes
+    pub fn with_signer<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let vault_id = self.vault_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"vault_config", &vault_id, &[self.bump]];
+        f(&[seeds])
+    }
+
+    pub fn distribute_rewards(&mut self, reward_amount: u64) -> Result<()> {
+        require!(self.total_deposited > 0, VaultCode::NoDepositors);
+
+        let increment = (reward_amount as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(VaultCode::MathOverflow)?
+            / self.total_deposited as u128;
+
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(increment)
+            .ok_or(VaultCode::MathOverflow)?;
+
+        Ok(())
+    }
 }
@@ -1,10 +1,27 @@
 #[cfg(test)]
 mod tests {
     use {
-        anchor_lang::{prelude::msg, AccountDeserialize, InstructionData, ToAccountMetas},
-        anchor_spl::associated_token::spl_associated_token_account,
+        anchor_lang::{
+            prelude::{msg, Clock},
+            AccountDeserialize, InstructionData, ToAccountMetas,
+        },
+        anchor_spl::{
+            associated_token::spl_associated_token_account,
+            token_2022::spl_token_2022::{
+                self,
+                extension::{
+                    interest_bearing_mint::InterestBearingConfig,
+                    transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+                    BaseStateWithExtensions, StateWithExtensions,
+                },
+                state::{Account as SplAccount, AccountState, Mint as SplMint},
+            },
+            token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata,
+        },
+        crate::error::VaultCode,
         litesvm::LiteSVM,
-        solana_instruction::{AccountMeta, Instruction},
+        litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, TOKEN_ID},
+        solana_instruction::{error::InstructionError, AccountMeta, Instruction},
         solana_keypair::Keypair,
         solana_message::Message,
         solana_native_token::LAMPORTS_PER_SOL,
@@ -12,6 +29,8 @@ mod tests {
         solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
         solana_signer::Signer,
         solana_transaction::Transaction,
+        solana_transaction_error::TransactionError,
+        std::future::Future,
     };
 
     static PROGRAM_ID: Pubkey = crate::ID;
@@ -42,10 +61,35 @@ mod tests {
         (program, payer)
     }
 
+    fn vault_config_pda(vault_id: u64) -> Pubkey {
+        Pubkey::find_program_address(&[b"vault_config", &vault_id.to_le_bytes()], &PROGRAM_ID).0
+    }
+
+    fn hook_config_pda(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"hook_config", mint.as_ref()], &TRANSFER_HOOK_PROGRAM_ID).0
+    }
+
+    /// Anchor discriminator of a transfer hook instruction
+    fn hook_ix_data(name: &str) -> Vec<u8> {
+        anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes()).to_bytes()
+            [..8]
+            .to_vec()
+    }
+
+    /// Polls a future that never waits, account fetches from LiteSVM resolve immediately
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        match std::pin::pin!(future).poll(&mut context) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("account fetch should resolve immediately"),
+        }
+    }
+
     struct VaultTestContext {
         program: LiteSVM,
         payer: Keypair,
         admin: Pubkey,
+        vault_id: u64,
         vault_config: Pubkey,
     }
 
@@ -54,18 +98,41 @@ mod tests {
             let (program, payer) = setup();
             let admin = payer.pubkey();
 
-            let vault_config = Pubkey::find_program_address(&[b"vault_config"], &PROGRAM_ID).0;
+            let vault_id = 0;
+            let vault_config = vault_config_pda(vault_id);
             msg!("Vault Config PDA: {}\n", vault_config);
 
             Self {
                 program,
                 payer,
                 admin,
+                vault_id,
                 vault_config,
             }
         }
 
+        /// Switches the context to another vault instance on the same deployment
+        fn use_vault(&mut self, vault_id: u64, admin: &Keypair) {
+            self.vault_id = vault_id;
+            self.vault_config = vault_config_pda(vault_id);
+            self.admin = admin.pubkey();
+            self.payer = admin.insecure_clone();
+        }
+
         fn execute_init_vault(&mut self) -> (Pubkey, Pubkey) {
+            self.execute_init_vault_with_args(crate::InitVaultArgs {
+                vault_id: self.vault_id,
+                decimals: 6,
+                name: "Vault Token".to_string(),
+                symbol: "VLT".to_string(),
+                uri: "https://example.com/vault.json".to_string(),
+                transfer_fee: None,
+                default_frozen: false,
+                interest_rate: None,
+            })
+        }
+
+        fn execute_init_vault_with_args(&mut self, args: crate::InitVaultArgs) -> (Pubkey, Pubkey) {
             let mint = Keypair::new();
             let vault = spl_associated_token_account::get_associated_token_address_with_program_id(
                 &self.vault_config,
@@ -89,7 +156,7 @@ mod tests {
                     system_program: SYSTEM_PROGRAM_ID,
                 }
                 .to_account_metas(None),
-                data: crate::instruction::InitializeVault {}.data(),
+                data: crate::instruction::InitializeVault { args }.data(),
             };
 
             let message = Message::new(&[init_vault_ix], Some(&self.payer.pubkey()));
@@ -107,7 +174,7 @@ mod tests {
             (mint.pubkey(), vault)
         }
 
-        fn execute_mint_token(&mut self, mint: &Pubkey, user: &Pubkey, amount: u64) -> Pubkey {
+        fn mint_token_ix(&self, mint: &Pubkey, user: &Pubkey, amount: u64) -> (Instruction, Pubkey) {
             let user_ata =
                 spl_associated_token_account::get_associated_token_address_with_program_id(
                     user,
@@ -115,11 +182,19 @@ mod tests {
                     &TOKEN_2022_PROGRAM_ID,
                 );
 
+            let minter = Pubkey::find_program_address(
+                &[b"minter", self.vault_config.as_ref(), self.admin.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
+
             let mint_token_ix = Instruction {
                 program_id: PROGRAM_ID,
                 accounts: crate::accounts::MintToken {
-                    admin: self.admin,
+                    authority: self.admin,
                     user: *user,
+                    vault_config: self.vault_config,
+                    minter,
                     mint: *mint,
                     user_ata,
                     associated_token_program: spl_associated_token_account::ID,
@@ -130,6 +205,12 @@ mod tests {
                 data: crate::instruction::MintToken { amount }.data(),
             };
 
+            (mint_token_ix, user_ata)
+        }
+
+        fn execute_mint_token(&mut self, mint: &Pubkey, user: &Pubkey, amount: u64) -> Pubkey {
+            let (mint_token_ix, user_ata) = self.mint_token_ix(mint, user, amount);
+
             let message = Message::new(&[mint_token_ix], Some(&self.payer.pubkey()));
             let transaction =
                 Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
@@ -142,19 +223,64 @@ mod tests {
             user_ata
         }
 
-        fn execute_deposit(&mut self, user: &Keypair, mint: &Pubkey, vault: &Pubkey, amount: u64) {
-            let amount_pda =
-                Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+        fn execute_grant_minter(
+            &mut self,
+            allowance: u64,
+            period_quota: u64,
+            period_seconds: i64,
+        ) -> Pubkey {
+            let minter = Pubkey::find_program_address(
+                &[b"minter", self.vault_config.as_ref(), self.admin.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
+
+            let grant_minter_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::GrantMinter {
+                    admin: self.admin,
+                    vault_config: self.vault_config,
+                    minter,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::GrantMinter {
+                    authority: self.admin,
+                    allowance,
+                    period_quota,
+                    period_seconds,
+                }
+                .data(),
+            };
+
+            let message = Message::new(&[grant_minter_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Grant minter transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+
+            minter
+        }
+
+        fn deposit_ix(&self, user: &Pubkey, mint: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+            let amount_pda = Pubkey::find_program_address(
+                &[b"amount", self.vault_config.as_ref(), user.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
 
             let user_ata =
                 spl_associated_token_account::get_associated_token_address_with_program_id(
-                    &user.pubkey(),
+                    user,
                     mint,
                     &TOKEN_2022_PROGRAM_ID,
                 );
 
             let mut accounts = crate::accounts::Deposit {
-                user: user.pubkey(),
+                user: *user,
                 amount_pda,
                 vault_config: self.vault_config,
                 mint: *mint,
@@ -166,36 +292,18 @@ mod tests {
             }
             .to_account_metas(None);
 
-            // Add extra accounts for transfer hook (order matters!)
-            let extra_account_meta_list = Pubkey::find_program_address(
-                &[b"extra-account-metas", mint.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
-
-            let source_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), user.pubkey().as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
-
-            let dest_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), self.vault_config.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
-
-            // Required order for transfer hook: program, extra_meta_list, then resolved accounts
-            accounts.push(AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false));
-            accounts.push(AccountMeta::new_readonly(extra_account_meta_list, false));
-            accounts.push(AccountMeta::new_readonly(source_whitelist, false));
-            accounts.push(AccountMeta::new_readonly(dest_whitelist, false));
+            // Extra accounts Token-2022 needs to invoke the transfer hook
+            accounts.extend(self.hook_accounts(&user_ata, mint, vault, user, amount));
 
-            let deposit_ix = Instruction {
+            Instruction {
                 program_id: PROGRAM_ID,
                 accounts,
                 data: crate::instruction::Deposit { amount }.data(),
-            };
+            }
+        }
+
+        fn execute_deposit(&mut self, user: &Keypair, mint: &Pubkey, vault: &Pubkey, amount: u64) {
+            let deposit_ix = self.deposit_ix(&user.pubkey(), mint, vault, amount);
 
             let message = Message::new(&[deposit_ix], Some(&user.pubkey()));
             let transaction = Transaction::new(&[user], message, self.program.latest_blockhash());
@@ -206,19 +314,73 @@ mod tests {
             msg!("Tx Signature: {}\n", tx.signature);
         }
 
-        fn execute_withdraw(&mut self, user: &Keypair, mint: &Pubkey, vault: &Pubkey, amount: u64) {
-            let amount_pda =
-                Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+        fn deposit_for_ix(
+            &self,
+            payer: &Pubkey,
+            beneficiary: &Pubkey,
+            mint: &Pubkey,
+            vault: &Pubkey,
+            amount: u64,
+            lock_until: Option<i64>,
+        ) -> Instruction {
+            let amount_pda = Pubkey::find_program_address(
+                &[b"amount", self.vault_config.as_ref(), beneficiary.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
+
+            let payer_ata =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    payer,
+                    mint,
+                    &TOKEN_2022_PROGRAM_ID,
+                );
+
+            let mut accounts = crate::accounts::DepositFor {
+                payer: *payer,
+                beneficiary: *beneficiary,
+                vault_config: self.vault_config,
+                amount_pda,
+                mint: *mint,
+                payer_ata,
+                vault: *vault,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_2022_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }
+            .to_account_metas(None);
+
+            // Only a lock-up needs the beneficiary's signature
+            if lock_until.is_some() {
+                accounts[1].is_signer = true;
+            }
+
+            // Extra accounts Token-2022 needs to invoke the transfer hook
+            accounts.extend(self.hook_accounts(&payer_ata, mint, vault, payer, amount));
+
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: crate::instruction::DepositFor { amount, lock_until }.data(),
+            }
+        }
+
+        fn withdraw_ix(&self, user: &Pubkey, mint: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+            let amount_pda = Pubkey::find_program_address(
+                &[b"amount", self.vault_config.as_ref(), user.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
 
             let user_ata =
                 spl_associated_token_account::get_associated_token_address_with_program_id(
-                    &user.pubkey(),
+                    user,
                     mint,
                     &TOKEN_2022_PROGRAM_ID,
                 );
 
             let mut accounts = crate::accounts::Withdraw {
-                user: user.pubkey(),
+                user: *user,
                 amount_pda,
                 vault_config: self.vault_config,
                 mint: *mint,
@@ -230,46 +392,165 @@ mod tests {
             }
             .to_account_metas(None);
 
-            // Add extra accounts for transfer hook (order matters!)
-            let extra_account_meta_list = Pubkey::find_program_address(
-                &[b"extra-account-metas", mint.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
+            // Extra accounts Token-2022 needs to invoke the transfer hook
+            let vault_config = self.vault_config;
+            accounts.extend(self.hook_accounts(vault, mint, &user_ata, &vault_config, amount));
 
-            let source_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), self.vault_config.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: crate::instruction::Withdraw { amount }.data(),
+            }
+        }
 
-            let dest_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), user.pubkey().as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
+        fn execute_withdraw(&mut self, user: &Keypair, mint: &Pubkey, vault: &Pubkey, amount: u64) {
+            let withdraw_ix = self.withdraw_ix(&user.pubkey(), mint, vault, amount);
+
+            let message = Message::new(&[withdraw_ix], Some(&user.pubkey()));
+            let transaction = Transaction::new(&[user], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Withdraw transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn execute_update_metadata(
+            &mut self,
+            mint: &Pubkey,
+            field: crate::MetadataField,
+            value: &str,
+        ) {
+            let update_metadata_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::UpdateMetadata {
+                    admin: self.admin,
+                    vault_config: self.vault_config,
+                    mint: *mint,
+                    token_program: TOKEN_2022_PROGRAM_ID,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::UpdateMetadata {
+                    field,
+                    value: value.to_string(),
+                }
+                .data(),
+            };
+
+            let message = Message::new(&[update_metadata_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Update metadata transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn execute_set_rate(&mut self, mint: &Pubkey, rate: i16) {
+            let set_rate_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetRate {
+                    admin: self.admin,
+                    vault_config: self.vault_config,
+                    mint: *mint,
+                    token_program: TOKEN_2022_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetRate { rate }.data(),
+            };
+
+            let message = Message::new(&[set_rate_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Set rate transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn execute_reconcile(&mut self, vault: &Pubkey) -> Pubkey {
+            let report = Pubkey::find_program_address(
+                &[b"reconciliation", self.vault_config.as_ref()],
+                &PROGRAM_ID,
             )
             .0;
 
-            // Required order for transfer hook: program, extra_meta_list, then resolved accounts
-            accounts.push(AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false));
-            accounts.push(AccountMeta::new_readonly(extra_account_meta_list, false));
-            accounts.push(AccountMeta::new_readonly(source_whitelist, false));
-            accounts.push(AccountMeta::new_readonly(dest_whitelist, false));
+            let reconcile_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::Reconcile {
+                    payer: self.payer.pubkey(),
+                    vault_config: self.vault_config,
+                    vault: *vault,
+                    report,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::Reconcile {}.data(),
+            };
 
-            let withdraw_ix = Instruction {
+            let message = Message::new(&[reconcile_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Reconcile transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+
+            report
+        }
+
+        fn execute_account_approval(&mut self, mint: &Pubkey, token_account: &Pubkey, approve: bool) {
+            let accounts = crate::accounts::AccountApproval {
+                admin: self.admin,
+                vault_config: self.vault_config,
+                mint: *mint,
+                token_account: *token_account,
+                token_program: TOKEN_2022_PROGRAM_ID,
+            }
+            .to_account_metas(None);
+
+            let data = if approve {
+                crate::instruction::ApproveAccount {}.data()
+            } else {
+                crate::instruction::RevokeAccount {}.data()
+            };
+
+            let approval_ix = Instruction {
                 program_id: PROGRAM_ID,
                 accounts,
-                data: crate::instruction::Withdraw { amount }.data(),
+                data,
             };
 
-            let message = Message::new(&[withdraw_ix], Some(&user.pubkey()));
-            let transaction = Transaction::new(&[user], message, self.program.latest_blockhash());
+            let message = Message::new(&[approval_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
             let tx = self.program.send_transaction(transaction).unwrap();
 
-            msg!("Withdraw transaction successful");
+            msg!("Account approval transaction successful");
             msg!("CUs Consumed: {}", tx.compute_units_consumed);
             msg!("Tx Signature: {}\n", tx.signature);
         }
 
+        fn get_token_account_state(&mut self, token_account: &Pubkey) -> AccountState {
+            let account = self.program.get_account(token_account).unwrap();
+            StateWithExtensions::<SplAccount>::unpack(&account.data)
+                .unwrap()
+                .base
+                .state
+        }
+
+        fn get_token_metadata(&mut self, mint: &Pubkey) -> TokenMetadata {
+            let account = self.program.get_account(mint).unwrap();
+            let mint_state = StateWithExtensions::<SplMint>::unpack(&account.data).unwrap();
+            mint_state
+                .get_variable_len_extension::<TokenMetadata>()
+                .unwrap()
+        }
+
         fn get_token_balance(&mut self, ata: &Pubkey) -> u64 {
             let account = self.program.get_account(ata).unwrap();
             let amount_offset = 64;
@@ -290,7 +571,228 @@ mod tests {
             }
         }
 
+        fn get_withheld_amount(&mut self, token_account: &Pubkey) -> u64 {
+            let account = self.program.get_account(token_account).unwrap();
+            let state = StateWithExtensions::<SplAccount>::unpack(&account.data).unwrap();
+            u64::from(state.get_extension::<TransferFeeAmount>().unwrap().withheld_amount)
+        }
+
+        fn get_mint_withheld_amount(&mut self, mint: &Pubkey) -> u64 {
+            let account = self.program.get_account(mint).unwrap();
+            let mint_state = StateWithExtensions::<SplMint>::unpack(&account.data).unwrap();
+            u64::from(mint_state.get_extension::<TransferFeeConfig>().unwrap().withheld_amount)
+        }
+
+        fn get_amount_ledger(&mut self, user: &Pubkey) -> Option<crate::state::Amount> {
+            let amount_pda = Pubkey::find_program_address(
+                &[b"amount", self.vault_config.as_ref(), user.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
+            let account = self.program.get_account(&amount_pda)?;
+            crate::state::Amount::try_deserialize(&mut account.data.as_ref()).ok()
+        }
+
+        fn get_vault_config(&mut self) -> crate::state::VaultConfig {
+            let account = self.program.get_account(&self.vault_config).unwrap();
+            crate::state::VaultConfig::try_deserialize(&mut account.data.as_ref()).unwrap()
+        }
+
+        fn execute_create_ata(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            let create_ata_ix =
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &self.payer.pubkey(),
+                    owner,
+                    mint,
+                    &TOKEN_2022_PROGRAM_ID,
+                );
+            let message = Message::new(&[create_ata_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            self.program.send_transaction(transaction).unwrap();
+
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                owner,
+                mint,
+                &TOKEN_2022_PROGRAM_ID,
+            )
+        }
+
+        fn execute_harvest_fees(&mut self, mint: &Pubkey, sources: &[Pubkey]) {
+            let mut accounts = crate::accounts::HarvestFees {
+                admin: self.admin,
+                vault_config: self.vault_config,
+                mint: *mint,
+                token_program: TOKEN_2022_PROGRAM_ID,
+            }
+            .to_account_metas(None);
+            accounts.extend(sources.iter().map(|source| AccountMeta::new(*source, false)));
+
+            let harvest_fees_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: crate::instruction::HarvestFees {}.data(),
+            };
+
+            let message = Message::new(&[harvest_fees_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Harvest fees transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn execute_withdraw_fees(&mut self, mint: &Pubkey, treasury: &Pubkey) {
+            let withdraw_fees_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::WithdrawFees {
+                    admin: self.admin,
+                    vault_config: self.vault_config,
+                    mint: *mint,
+                    treasury: *treasury,
+                    token_program: TOKEN_2022_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::WithdrawFees {}.data(),
+            };
+
+            let message = Message::new(&[withdraw_fees_ix], Some(&self.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Withdraw fees transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn fund_rewards_ix(
+            &self,
+            reward_mint: &Pubkey,
+            reward_token_program: &Pubkey,
+            amount: u64,
+        ) -> Instruction {
+            let admin_reward_ata =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &self.admin,
+                    reward_mint,
+                    reward_token_program,
+                );
+            let reward_vault =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &self.vault_config,
+                    reward_mint,
+                    reward_token_program,
+                );
+
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::FundRewards {
+                    admin: self.admin,
+                    vault_config: self.vault_config,
+                    reward_mint: *reward_mint,
+                    admin_reward_ata,
+                    reward_vault,
+                    associated_token_program: spl_associated_token_account::ID,
+                    reward_token_program: *reward_token_program,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::FundRewards { amount }.data(),
+            }
+        }
+
+        fn execute_claim_rewards(&mut self, user: &Keypair, reward_mint: &Pubkey) -> Pubkey {
+            let amount_pda = Pubkey::find_program_address(
+                &[b"amount", self.vault_config.as_ref(), user.pubkey().as_ref()],
+                &PROGRAM_ID,
+            )
+            .0;
+            let reward_vault =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &self.vault_config,
+                    reward_mint,
+                    &TOKEN_ID,
+                );
+            let user_reward_ata =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &user.pubkey(),
+                    reward_mint,
+                    &TOKEN_ID,
+                );
+
+            let claim_rewards_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::ClaimRewards {
+                    user: user.pubkey(),
+                    vault_config: self.vault_config,
+                    amount_pda,
+                    reward_mint: *reward_mint,
+                    reward_vault,
+                    user_reward_ata,
+                    associated_token_program: spl_associated_token_account::ID,
+                    reward_token_program: TOKEN_ID,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::ClaimRewards {}.data(),
+            };
+
+            let message = Message::new(&[claim_rewards_ix], Some(&user.pubkey()));
+            let transaction = Transaction::new(&[user], message, self.program.latest_blockhash());
+            let tx = self.program.send_transaction(transaction).unwrap();
+
+            msg!("Claim rewards transaction successful");
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+
+            user_reward_ata
+        }
+
         // Transfer Hook Helper Methods
+
+        /// Accounts Token-2022 needs to invoke the mint's transfer hook, resolved from the
+        /// ExtraAccountMetaList the same way a client would, for use as remaining accounts
+        fn hook_accounts(
+            &self,
+            source: &Pubkey,
+            mint: &Pubkey,
+            destination: &Pubkey,
+            authority: &Pubkey,
+            amount: u64,
+        ) -> Vec<AccountMeta> {
+            let mint_account = self.program.get_account(mint).unwrap();
+            let decimals = StateWithExtensions::<SplMint>::unpack(&mint_account.data)
+                .unwrap()
+                .base
+                .decimals;
+
+            let transfer_ix = block_on(
+                spl_token_2022::offchain::create_transfer_checked_instruction_with_extra_metas(
+                    &TOKEN_2022_PROGRAM_ID,
+                    source,
+                    mint,
+                    destination,
+                    authority,
+                    &[],
+                    amount,
+                    decimals,
+                    |address| {
+                        std::future::ready(Ok(self
+                            .program
+                            .get_account(&address)
+                            .map(|account| account.data)))
+                    },
+                ),
+            )
+            .unwrap();
+
+            // Skip source, mint, destination and authority
+            transfer_ix.accounts[4..].to_vec()
+        }
+
         fn execute_initialize_transfer_hook(&mut self, mint: &Pubkey) -> Pubkey {
             let extra_account_meta_list = Pubkey::find_program_address(
                 &[b"extra-account-metas", mint.as_ref()],
@@ -300,19 +802,20 @@ mod tests {
 
             msg!("Extra Account Meta List: {}\n", extra_account_meta_list);
 
-            // Calculate discriminator using Anchor's method
-            let discriminator =
-                anchor_lang::solana_program::hash::hash(b"global:initialize_transfer_hook");
-            let mut data = vec![];
-            data.extend_from_slice(&discriminator.to_bytes()[..8]);
+            // The hook answers to the SPL interface discriminator, not Anchor's
+            let discriminator = anchor_lang::solana_program::hash::hash(
+                b"spl-transfer-hook-interface:initialize-extra-account-metas",
+            );
+            let data = discriminator.to_bytes()[..8].to_vec();
 
             let init_hook_ix = Instruction {
                 program_id: TRANSFER_HOOK_PROGRAM_ID,
                 accounts: vec![
-                    anchor_lang::prelude::AccountMeta::new(self.payer.pubkey(), true),
-                    anchor_lang::prelude::AccountMeta::new(extra_account_meta_list, false),
-                    anchor_lang::prelude::AccountMeta::new_readonly(*mint, false),
-                    anchor_lang::prelude::AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new(extra_account_meta_list, false),
+                    AccountMeta::new_readonly(*mint, false),
+                    AccountMeta::new(hook_config_pda(mint), false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 ],
                 data,
             };
@@ -338,19 +841,20 @@ mod tests {
 
             msg!("Whitelisting: {} -> {}\n", user, whitelist_entry);
 
-            // Calculate discriminator using Anchor's method
-            let discriminator = anchor_lang::solana_program::hash::hash(b"global:add_to_whitelist");
-            let mut data = vec![];
-            data.extend_from_slice(&discriminator.to_bytes()[..8]);
+            // user, valid_from, valid_until (0 = never expires)
+            let mut data = hook_ix_data("add_to_whitelist");
             data.extend_from_slice(user.as_ref());
+            data.extend_from_slice(&0i64.to_le_bytes());
+            data.extend_from_slice(&0i64.to_le_bytes());
 
             let add_whitelist_ix = Instruction {
                 program_id: TRANSFER_HOOK_PROGRAM_ID,
                 accounts: vec![
-                    anchor_lang::prelude::AccountMeta::new(self.payer.pubkey(), true),
-                    anchor_lang::prelude::AccountMeta::new_readonly(*mint, false),
-                    anchor_lang::prelude::AccountMeta::new(whitelist_entry, false),
-                    anchor_lang::prelude::AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new_readonly(*mint, false),
+                    AccountMeta::new_readonly(hook_config_pda(mint), false),
+                    AccountMeta::new(whitelist_entry, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 ],
                 data,
             };
@@ -374,19 +878,16 @@ mod tests {
             )
             .0;
 
-            // Calculate discriminator using Anchor's method
-            let discriminator =
-                anchor_lang::solana_program::hash::hash(b"global:remove_from_whitelist");
-            let mut data = vec![];
-            data.extend_from_slice(&discriminator.to_bytes()[..8]);
+            let mut data = hook_ix_data("remove_from_whitelist");
             data.extend_from_slice(user.as_ref());
 
             let remove_whitelist_ix = Instruction {
                 program_id: TRANSFER_HOOK_PROGRAM_ID,
                 accounts: vec![
-                    anchor_lang::prelude::AccountMeta::new(self.payer.pubkey(), true),
-                    anchor_lang::prelude::AccountMeta::new_readonly(*mint, false),
-                    anchor_lang::prelude::AccountMeta::new(whitelist_entry, false),
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new_readonly(*mint, false),
+                    AccountMeta::new(hook_config_pda(mint), false),
+                    AccountMeta::new(whitelist_entry, false),
                 ],
                 data,
             };
@@ -401,70 +902,78 @@ mod tests {
             msg!("Tx Signature: {}\n", tx.signature);
         }
 
-        fn execute_emergency_transfer(
+        /// Hooked vault mint whose hook lets the vault and `users` transfer
+        fn setup_hooked_vault(
             &mut self,
+            args: crate::InitVaultArgs,
+            users: &[Pubkey],
+        ) -> (Pubkey, Pubkey) {
+            let (mint, vault) = self.execute_init_vault_with_args(args);
+            self.execute_initialize_transfer_hook(&mint);
+
+            let vault_config = self.vault_config;
+            self.execute_add_to_whitelist(&mint, &vault_config);
+            for user in users {
+                self.execute_add_to_whitelist(&mint, user);
+            }
+
+            (mint, vault)
+        }
+
+        fn admin_transfer_ix(
+            &self,
             mint: &Pubkey,
             from_account: &Pubkey,
             to_account: &Pubkey,
             amount: u64,
-        ) {
-            // Get owners from token accounts
-            let from_account_data = self.program.get_account(from_account).unwrap();
-            let owner_offset = 32;
-            let from_owner =
-                Pubkey::try_from(&from_account_data.data[owner_offset..owner_offset + 32]).unwrap();
-
-            let to_account_data = self.program.get_account(to_account).unwrap();
-            let to_owner =
-                Pubkey::try_from(&to_account_data.data[owner_offset..owner_offset + 32]).unwrap();
-
-            let mut accounts = crate::accounts::EmergencyTransfer {
+            depositor: Option<Pubkey>,
+        ) -> Instruction {
+            let ledger = depositor.map(|depositor| {
+                Pubkey::find_program_address(
+                    &[b"amount", self.vault_config.as_ref(), depositor.as_ref()],
+                    &PROGRAM_ID,
+                )
+                .0
+            });
+
+            let mut accounts = crate::accounts::AdminTransfer {
                 admin: self.admin,
                 vault_config: self.vault_config,
                 mint: *mint,
                 from_account: *from_account,
                 to_account: *to_account,
+                ledger,
                 token_program: TOKEN_2022_PROGRAM_ID,
             }
             .to_account_metas(None);
 
-            // Add extra accounts for transfer hook (order matters!)
-            let extra_account_meta_list = Pubkey::find_program_address(
-                &[b"extra-account-metas", mint.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
-
-            let source_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), from_owner.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
-
-            let dest_whitelist = Pubkey::find_program_address(
-                &[b"whitelist", mint.as_ref(), to_owner.as_ref()],
-                &TRANSFER_HOOK_PROGRAM_ID,
-            )
-            .0;
+            // The admin moves tokens as the permanent delegate
+            accounts.extend(self.hook_accounts(from_account, mint, to_account, &self.admin, amount));
 
-            // Required order for transfer hook: program, extra_meta_list, then resolved accounts
-            accounts.push(AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false));
-            accounts.push(AccountMeta::new_readonly(extra_account_meta_list, false));
-            accounts.push(AccountMeta::new_readonly(source_whitelist, false));
-            accounts.push(AccountMeta::new_readonly(dest_whitelist, false));
-
-            let emergency_transfer_ix = Instruction {
+            Instruction {
                 program_id: PROGRAM_ID,
                 accounts,
-                data: crate::instruction::EmergencyTransfer { amount }.data(),
-            };
+                data: crate::instruction::AdminTransfer { amount, depositor }.data(),
+            }
+        }
 
-            let message = Message::new(&[emergency_transfer_ix], Some(&self.payer.pubkey()));
+        fn execute_admin_transfer(
+            &mut self,
+            mint: &Pubkey,
+            from_account: &Pubkey,
+            to_account: &Pubkey,
+            amount: u64,
+            depositor: Option<Pubkey>,
+        ) {
+            let admin_transfer_ix =
+                self.admin_transfer_ix(mint, from_account, to_account, amount, depositor);
+
+            let message = Message::new(&[admin_transfer_ix], Some(&self.payer.pubkey()));
             let transaction =
                 Transaction::new(&[&self.payer], message, self.program.latest_blockhash());
             let tx = self.program.send_transaction(transaction).unwrap();
 
-            msg!("Emergency transfer successful");
+            msg!("Admin transfer successful");
             msg!("CUs Consumed: {}", tx.compute_units_consumed);
             msg!("Tx Signature: {}\n", tx.signature);
         }
@@ -500,6 +1009,707 @@ mod tests {
         msg!("✓ VaultConfig state verified");
     }
 
+    #[test]
+    pub fn test_init_vault_writes_token_metadata() {
+        let mut ctx = VaultTestContext::new();
+        let (mint, _vault) = ctx.execute_init_vault();
+
+        let metadata = ctx.get_token_metadata(&mint);
+        assert_eq!(metadata.mint, mint);
+        assert_eq!(metadata.name, "Vault Token");
+        assert_eq!(metadata.symbol, "VLT");
+        assert_eq!(metadata.uri, "https://example.com/vault.json");
+        assert_eq!(
+            Option::<Pubkey>::from(metadata.update_authority),
+            Some(ctx.vault_config)
+        );
+
+        // Admin updates a field; the longer value forces a mint realloc
+        ctx.execute_update_metadata(
+            &mint,
+            crate::MetadataField::Name,
+            "Vault Token (Series A Deposits)",
+        );
+
+        let metadata = ctx.get_token_metadata(&mint);
+        assert_eq!(metadata.name, "Vault Token (Series A Deposits)");
+        assert_eq!(metadata.symbol, "VLT");
+
+        msg!("✓ Mint metadata initialized on-mint");
+        msg!("✓ Metadata updated by admin");
+    }
+
+    #[test]
+    pub fn test_init_vault_with_transfer_fee() {
+        let mut ctx = VaultTestContext::new();
+        let (mint, _vault) = ctx.execute_init_vault_with_args(crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 9,
+            name: "Fee Vault Token".to_string(),
+            symbol: "FVT".to_string(),
+            uri: "https://example.com/fee-vault.json".to_string(),
+            transfer_fee: Some(crate::TransferFeeArgs {
+                basis_points: 50,
+                maximum_fee: 5_000,
+            }),
+            default_frozen: false,
+            interest_rate: None,
+        });
+
+        let account = ctx.program.get_account(&mint).unwrap();
+        let mint_state = StateWithExtensions::<SplMint>::unpack(&account.data).unwrap();
+        assert_eq!(mint_state.base.decimals, 9);
+
+        let transfer_fee_config = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+        let fee = transfer_fee_config.get_epoch_fee(0);
+        assert_eq!(u16::from(fee.transfer_fee_basis_points), 50);
+        assert_eq!(u64::from(fee.maximum_fee), 5_000);
+        assert_eq!(
+            Option::<Pubkey>::from(transfer_fee_config.withdraw_withheld_authority),
+            Some(ctx.vault_config)
+        );
+
+        // 0.5% of 100_000 is withheld by Token-2022 on every vault transfer
+        assert_eq!(transfer_fee_config.calculate_epoch_fee(0, 100_000), Some(500));
+
+        msg!("✓ TransferFeeConfig initialized on the vault mint");
+    }
+
+    #[test]
+    pub fn test_init_vault_with_interest_rate() {
+        let mut ctx = VaultTestContext::new();
+        let (mint, _vault) = ctx.execute_init_vault_with_args(crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "Yield Vault Token".to_string(),
+            symbol: "YVT".to_string(),
+            uri: "https://example.com/yield-vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: false,
+            interest_rate: Some(500),
+        });
+
+        let account = ctx.program.get_account(&mint).unwrap();
+        let mint_state = StateWithExtensions::<SplMint>::unpack(&account.data).unwrap();
+        let interest_config = mint_state.get_extension::<InterestBearingConfig>().unwrap();
+        assert_eq!(i16::from(interest_config.current_rate), 500);
+        assert_eq!(
+            Option::<Pubkey>::from(interest_config.rate_authority),
+            Some(ctx.vault_config)
+        );
+
+        ctx.execute_set_rate(&mint, 250);
+
+        let account = ctx.program.get_account(&mint).unwrap();
+        let mint_state = StateWithExtensions::<SplMint>::unpack(&account.data).unwrap();
+        let interest_config = mint_state.get_extension::<InterestBearingConfig>().unwrap();
+        assert_eq!(i16::from(interest_config.current_rate), 250);
+
+        msg!("✓ InterestBearingConfig initialized with the vault config as rate authority");
+        msg!("✓ Rate updated by admin");
+    }
+
+    #[test]
+    pub fn test_independent_vaults_on_one_deployment() {
+        let mut ctx = VaultTestContext::new();
+        let (mint_a, vault_a) = ctx.execute_init_vault();
+        let vault_config_a = ctx.vault_config;
+        let admin_a = ctx.admin;
+
+        // A second team runs its own vault with its own admin
+        let team_b_admin = Keypair::new();
+        ctx.program
+            .airdrop(&team_b_admin.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        ctx.use_vault(7, &team_b_admin);
+        let (mint_b, vault_b) = ctx.execute_init_vault();
+        let vault_config_b = ctx.vault_config;
+
+        assert_ne!(vault_config_a, vault_config_b);
+        assert_ne!(vault_a, vault_b);
+
+        for (vault_config, vault_id, admin, mint) in [
+            (vault_config_a, 0, admin_a, mint_a),
+            (vault_config_b, 7, team_b_admin.pubkey(), mint_b),
+        ] {
+            let account = ctx.program.get_account(&vault_config).unwrap();
+            let data =
+                crate::state::VaultConfig::try_deserialize(&mut account.data.as_ref()).unwrap();
+            assert_eq!(data.vault_id, vault_id);
+            assert_eq!(data.admin, admin);
+            assert_eq!(data.mint, mint);
+        }
+
+        msg!("✓ Two vault instances initialized side by side");
+    }
+
+    #[test]
+    pub fn test_default_frozen_accounts_need_approval() {
+        let mut ctx = VaultTestContext::new();
+        let (mint, vault) = ctx.execute_init_vault_with_args(crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "KYC Vault Token".to_string(),
+            symbol: "KVT".to_string(),
+            uri: "https://example.com/kyc-vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: true,
+            interest_rate: None,
+        });
+
+        // The vault's own ATA is approved during init
+        assert_eq!(ctx.get_token_account_state(&vault), AccountState::Initialized);
+
+        // Minting to a holder who hasn't been approved fails, even though the ATA is created
+        ctx.execute_grant_minter(1_000_000, 1_000_000, 86_400);
+        let send_mint_token = |ctx: &mut VaultTestContext, user: &Pubkey| {
+            let (mint_token_ix, _) = ctx.mint_token_ix(&mint, user, 1_000);
+            let message = Message::new(&[mint_token_ix], Some(&ctx.payer.pubkey()));
+            let transaction =
+                Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+            ctx.program
+                .send_transaction(transaction)
+                .map(|_| ())
+                .map_err(|failure| failure.err)
+        };
+        let not_approved = Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(VaultCode::AccountNotApproved.into()),
+        ));
+        assert_eq!(send_mint_token(&mut ctx, &Pubkey::new_unique()), not_approved);
+
+        // A new holder's ATA starts out frozen
+        let user = Keypair::new();
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &ctx.payer.pubkey(),
+                &user.pubkey(),
+                &mint,
+                &TOKEN_2022_PROGRAM_ID,
+            );
+        let message = Message::new(&[create_ata_ix], Some(&ctx.payer.pubkey()));
+        let transaction =
+            Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        ctx.program.send_transaction(transaction).unwrap();
+
+        let user_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &user.pubkey(),
+            &mint,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        assert_eq!(ctx.get_token_account_state(&user_ata), AccountState::Frozen);
+        assert_eq!(send_mint_token(&mut ctx, &user.pubkey()), not_approved);
+
+        ctx.execute_account_approval(&mint, &user_ata, true);
+        assert_eq!(ctx.get_token_account_state(&user_ata), AccountState::Initialized);
+
+        send_mint_token(&mut ctx, &user.pubkey()).unwrap();
+        assert_eq!(ctx.get_token_balance(&user_ata), 1_000);
+
+        ctx.execute_account_approval(&mint, &user_ata, false);
+        assert_eq!(ctx.get_token_account_state(&user_ata), AccountState::Frozen);
+        assert_eq!(send_mint_token(&mut ctx, &user.pubkey()), not_approved);
+
+        msg!("✓ New holders start frozen");
+        msg!("✓ Minting waits for the admin's approval");
+        msg!("✓ Admin approves and revokes holders");
+    }
+
+    #[test]
+    pub fn test_minter_quota_enforced() {
+        let mut ctx = VaultTestContext::new();
+        let (mint, _vault) = ctx.execute_init_vault();
+
+        // Admin grants itself a minter role: 1M lifetime, 600k per day
+        let minter = ctx.execute_grant_minter(1_000_000, 600_000, 86_400);
+
+        let user = Keypair::new();
+        let user_ata = ctx.execute_mint_token(&mint, &user.pubkey(), 500_000);
+        assert_eq!(ctx.get_token_balance(&user_ata), 500_000);
+
+        // 500k + 200k would exceed the daily quota
+        let (mint_token_ix, _) = ctx.mint_token_ix(&mint, &user.pubkey(), 200_000);
+        let message = Message::new(&[mint_token_ix], Some(&ctx.payer.pubkey()));
+        let transaction =
+            Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+        assert!(result.is_err(), "mint over the period quota should fail");
+
+        let minter_account = ctx.program.get_account(&minter).unwrap();
+        let minter_data =
+            crate::state::Minter::try_deserialize(&mut minter_account.data.as_ref()).unwrap();
+        assert_eq!(minter_data.total_minted, 500_000);
+        assert_eq!(minter_data.minted_in_period, 500_000);
+
+        msg!("✓ Minter quota debited and enforced");
+    }
+
+    #[test]
+    pub fn test_reconcile_fresh_vault() {
+        let mut ctx = VaultTestContext::new();
+        let (_mint, vault) = ctx.execute_init_vault();
+
+        let report = ctx.execute_reconcile(&vault);
+
+        let report_account = ctx.program.get_account(&report).unwrap();
+        let report_data = crate::state::ReconciliationReport::try_deserialize(
+            &mut report_account.data.as_ref(),
+        )
+        .unwrap();
+
+        assert_eq!(report_data.vault_config, ctx.vault_config);
+        assert_eq!(report_data.vault_balance, 0);
+        assert_eq!(report_data.total_deposited, 0);
+        assert_eq!(report_data.surplus, 0);
+        assert_eq!(report_data.deficit, 0);
+
+        msg!("✓ Reconciliation report written");
+    }
+
+    #[test]
+    pub fn test_deposit_requires_whitelisted_user() {
+        let mut ctx = VaultTestContext::new();
+        let user = Keypair::new();
+        ctx.program
+            .airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let args = crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "Vault Token".to_string(),
+            symbol: "VLT".to_string(),
+            uri: "https://example.com/vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: false,
+            interest_rate: None,
+        };
+        let (mint, vault) = ctx.setup_hooked_vault(args, &[user.pubkey()]);
+        ctx.execute_grant_minter(10_000_000, 10_000_000, 86_400);
+        ctx.execute_mint_token(&mint, &user.pubkey(), 1_000_000);
+
+        // The hook accounts are forwarded through the vault's CPI
+        ctx.execute_deposit(&user, &mint, &vault, 400_000);
+        assert_eq!(ctx.get_token_balance(&vault), 400_000);
+
+        ctx.execute_remove_from_whitelist(&mint, &user.pubkey());
+        let deposit_ix = ctx.deposit_ix(&user.pubkey(), &mint, &vault, 100_000);
+        let message = Message::new(&[deposit_ix], Some(&user.pubkey()));
+        let transaction = Transaction::new(&[&user], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+        assert!(result.is_err(), "deposit by a removed user should fail");
+        assert_eq!(ctx.get_token_balance(&vault), 400_000);
+
+        msg!("✓ Hooked deposit succeeds for a whitelisted user");
+        msg!("✓ Hook rejects the deposit once the user is removed");
+    }
+
+    #[test]
+    pub fn test_transfer_fee_credited_net_and_harvested() {
+        let mut ctx = VaultTestContext::new();
+        let user = Keypair::new();
+        ctx.program
+            .airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let (mint, vault) = ctx.setup_hooked_vault(
+            crate::InitVaultArgs {
+                vault_id: ctx.vault_id,
+                decimals: 6,
+                name: "Fee Vault Token".to_string(),
+                symbol: "FVT".to_string(),
+                uri: "https://example.com/fee-vault.json".to_string(),
+                transfer_fee: Some(crate::TransferFeeArgs {
+                    basis_points: 50,
+                    maximum_fee: 5_000,
+                }),
+                default_frozen: false,
+                interest_rate: None,
+            },
+            &[user.pubkey()],
+        );
+        ctx.execute_grant_minter(10_000_000, 10_000_000, 86_400);
+        let user_ata = ctx.execute_mint_token(&mint, &user.pubkey(), 1_000_000);
+
+        // 0.5% of 100_000 is withheld in the vault's ATA, only the rest is credited
+        ctx.execute_deposit(&user, &mint, &vault, 100_000);
+        assert_eq!(ctx.get_token_balance(&vault), 99_500);
+        assert_eq!(ctx.get_withheld_amount(&vault), 500);
+        assert_eq!(ctx.get_amount_ledger(&user.pubkey()).unwrap().amount, 99_500);
+        assert_eq!(ctx.get_vault_config().total_deposited, 99_500);
+
+        // Withdrawing the credited amount empties the vault, the fee is withheld at the user
+        ctx.execute_withdraw(&user, &mint, &vault, 99_500);
+        assert_eq!(ctx.get_token_balance(&vault), 0);
+        assert_eq!(ctx.get_token_balance(&user_ata), 900_000 + 99_500 - 498);
+        assert_eq!(ctx.get_withheld_amount(&user_ata), 498);
+        assert_eq!(ctx.get_vault_config().total_deposited, 0);
+        let amount_pda = Pubkey::find_program_address(
+            &[b"amount", ctx.vault_config.as_ref(), user.pubkey().as_ref()],
+            &PROGRAM_ID,
+        )
+        .0;
+        ctx.assert_account_closed(&amount_pda, "Amount PDA");
+
+        ctx.execute_harvest_fees(&mint, &[vault, user_ata]);
+        assert_eq!(ctx.get_withheld_amount(&vault), 0);
+        assert_eq!(ctx.get_withheld_amount(&user_ata), 0);
+        assert_eq!(ctx.get_mint_withheld_amount(&mint), 998);
+
+        let admin = ctx.admin;
+        let treasury = ctx.execute_create_ata(&admin, &mint);
+        ctx.execute_withdraw_fees(&mint, &treasury);
+        assert_eq!(ctx.get_mint_withheld_amount(&mint), 0);
+        assert_eq!(ctx.get_token_balance(&treasury), 998);
+
+        msg!("✓ Deposits credit the amount net of the transfer fee");
+        msg!("✓ Withheld fees harvested into the mint");
+        msg!("✓ Harvested fees withdrawn to the treasury");
+    }
+
+    #[test]
+    pub fn test_claim_rewards_pro_rata() {
+        let mut ctx = VaultTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        for user in [&alice, &bob] {
+            ctx.program
+                .airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL)
+                .unwrap();
+        }
+
+        let args = crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "Vault Token".to_string(),
+            symbol: "VLT".to_string(),
+            uri: "https://example.com/vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: false,
+            interest_rate: None,
+        };
+        let admin = ctx.admin;
+        let (mint, vault) = ctx.setup_hooked_vault(args, &[alice.pubkey(), bob.pubkey()]);
+        ctx.execute_grant_minter(10_000_000, 10_000_000, 86_400);
+
+        // Rewards are paid in a separate, plain SPL token
+        let reward_mint = CreateMint::new(&mut ctx.program, &ctx.payer)
+            .authority(&admin)
+            .decimals(6)
+            .send()
+            .unwrap();
+        let admin_reward_ata =
+            CreateAssociatedTokenAccount::new(&mut ctx.program, &ctx.payer, &reward_mint)
+                .owner(&admin)
+                .send()
+                .unwrap();
+        MintTo::new(&mut ctx.program, &ctx.payer, &reward_mint, &admin_reward_ata, 10_000)
+            .send()
+            .unwrap();
+
+        let send_fund_rewards =
+            |ctx: &mut VaultTestContext, reward_mint: &Pubkey, token_program: &Pubkey| {
+                let fund_rewards_ix = ctx.fund_rewards_ix(reward_mint, token_program, 1_000);
+                let message = Message::new(&[fund_rewards_ix], Some(&ctx.payer.pubkey()));
+                let transaction =
+                    Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+                ctx.program
+                    .send_transaction(transaction)
+                    .map(|_| ())
+                    .map_err(|failure| failure.err)
+            };
+
+        // Nobody has deposited yet, so funding fails and the admin keeps the tokens
+        let result = send_fund_rewards(&mut ctx, &reward_mint, &TOKEN_ID);
+        assert_eq!(
+            result,
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(VaultCode::NoDepositors.into())
+            ))
+        );
+        assert_eq!(ctx.get_token_balance(&admin_reward_ata), 10_000);
+
+        for (user, amount) in [(&alice, 300_000), (&bob, 100_000)] {
+            ctx.execute_mint_token(&mint, &user.pubkey(), amount);
+            ctx.execute_deposit(user, &mint, &vault, amount);
+        }
+
+        // The vault's own mint can't double as the reward mint
+        ctx.execute_mint_token(&mint, &admin, 10_000);
+        let result = send_fund_rewards(&mut ctx, &mint, &TOKEN_2022_PROGRAM_ID);
+        assert_eq!(
+            result,
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(VaultCode::InvalidRewardMint.into())
+            ))
+        );
+
+        send_fund_rewards(&mut ctx, &reward_mint, &TOKEN_ID).unwrap();
+
+        let alice_reward_ata = ctx.execute_claim_rewards(&alice, &reward_mint);
+        let bob_reward_ata = ctx.execute_claim_rewards(&bob, &reward_mint);
+        assert_eq!(ctx.get_token_balance(&alice_reward_ata), 750);
+        assert_eq!(ctx.get_token_balance(&bob_reward_ata), 250);
+        assert_eq!(ctx.get_amount_ledger(&alice.pubkey()).unwrap().pending_rewards, 0);
+
+        msg!("✓ Funding rejected without depositors and for the vault mint");
+        msg!("✓ Rewards claimed on-chain pro-rata to deposits");
+    }
+
+    #[test]
+    pub fn test_deposit_for_lock_needs_beneficiary() {
+        let mut ctx = VaultTestContext::new();
+        let payer = Keypair::new();
+        let beneficiary = Keypair::new();
+        for user in [&payer, &beneficiary] {
+            ctx.program
+                .airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL)
+                .unwrap();
+        }
+
+        let args = crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "Vault Token".to_string(),
+            symbol: "VLT".to_string(),
+            uri: "https://example.com/vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: false,
+            interest_rate: None,
+        };
+        let (mint, vault) =
+            ctx.setup_hooked_vault(args, &[payer.pubkey(), beneficiary.pubkey()]);
+        ctx.execute_grant_minter(10_000_000, 10_000_000, 86_400);
+        ctx.execute_mint_token(&mint, &payer.pubkey(), 1_000_000);
+        let beneficiary_ata = ctx.execute_create_ata(&beneficiary.pubkey(), &mint);
+
+        let send_deposit_for = |ctx: &mut VaultTestContext,
+                                signers: &[&Keypair],
+                                amount: u64,
+                                lock_until: Option<i64>| {
+            let deposit_for_ix = ctx.deposit_for_ix(
+                &payer.pubkey(),
+                &beneficiary.pubkey(),
+                &mint,
+                &vault,
+                amount,
+                lock_until,
+            );
+            let message = Message::new(&[deposit_for_ix], Some(&payer.pubkey()));
+            let transaction = Transaction::new(signers, message, ctx.program.latest_blockhash());
+            ctx.program
+                .send_transaction(transaction)
+                .map(|_| ())
+                .map_err(|failure| failure.err)
+        };
+
+        // A gift without a lock-up needs only the payer
+        send_deposit_for(&mut ctx, &[&payer], 100_000, None).unwrap();
+        let ledger = ctx.get_amount_ledger(&beneficiary.pubkey()).unwrap();
+        assert_eq!(ledger.amount, 100_000);
+        assert_eq!(ledger.lock_until, 0);
+
+        // The payer can't lock the beneficiary's balance forever with a dust deposit
+        let beneficiary_meta = AccountMeta::new_readonly(beneficiary.pubkey(), false);
+        let mut griefing_ix = ctx.deposit_for_ix(
+            &payer.pubkey(),
+            &beneficiary.pubkey(),
+            &mint,
+            &vault,
+            1,
+            Some(i64::MAX),
+        );
+        griefing_ix.accounts[1] = beneficiary_meta;
+        let message = Message::new(&[griefing_ix], Some(&payer.pubkey()));
+        let transaction = Transaction::new(&[&payer], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+        assert_eq!(
+            result.err().map(|failure| failure.err),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(VaultCode::LockRequiresBeneficiary.into())
+            ))
+        );
+        assert_eq!(ctx.get_amount_ledger(&beneficiary.pubkey()).unwrap().lock_until, 0);
+
+        // With the beneficiary co-signing, the lock-up applies
+        let now = ctx.program.get_sysvar::<Clock>().unix_timestamp;
+        let lock_until = now + 86_400;
+        send_deposit_for(&mut ctx, &[&payer, &beneficiary], 100_000, Some(lock_until)).unwrap();
+        assert_eq!(
+            ctx.get_amount_ledger(&beneficiary.pubkey()).unwrap().lock_until,
+            lock_until
+        );
+
+        let withdraw_ix = ctx.withdraw_ix(&beneficiary.pubkey(), &mint, &vault, 200_000);
+        let message = Message::new(&[withdraw_ix], Some(&beneficiary.pubkey()));
+        let transaction =
+            Transaction::new(&[&beneficiary], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+        assert_eq!(
+            result.err().map(|failure| failure.err),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(VaultCode::FundsLocked.into())
+            ))
+        );
+
+        // Once the lock-up has passed the beneficiary withdraws everything
+        let mut clock = ctx.program.get_sysvar::<Clock>();
+        clock.unix_timestamp = lock_until;
+        ctx.program.set_sysvar::<Clock>(&clock);
+        ctx.execute_withdraw(&beneficiary, &mint, &vault, 200_000);
+        assert_eq!(ctx.get_token_balance(&beneficiary_ata), 200_000);
+
+        msg!("✓ deposit_for credits the beneficiary");
+        msg!("✓ Lock-up without the beneficiary's signature rejected");
+        msg!("✓ Withdrawals blocked until the lock-up passes");
+    }
+
+    #[test]
+    pub fn test_reconcile_tracks_deposits_and_clawbacks() {
+        let mut ctx = VaultTestContext::new();
+        let user = Keypair::new();
+        ctx.program
+            .airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let args = crate::InitVaultArgs {
+            vault_id: ctx.vault_id,
+            decimals: 6,
+            name: "Vault Token".to_string(),
+            symbol: "VLT".to_string(),
+            uri: "https://example.com/vault.json".to_string(),
+            transfer_fee: None,
+            default_frozen: false,
+            interest_rate: None,
+        };
+        let admin = ctx.admin;
+        let (mint, vault) = ctx.setup_hooked_vault(args, &[user.pubkey(), admin]);
+        ctx.execute_grant_minter(10_000_000, 10_000_000, 86_400);
+        ctx.execute_mint_token(&mint, &user.pubkey(), 1_000_000);
+        let admin_ata = ctx.execute_create_ata(&admin, &mint);
+
+        let read_report = |ctx: &mut VaultTestContext| {
+            let report = ctx.execute_reconcile(&vault);
+            let report_account = ctx.program.get_account(&report).unwrap();
+            crate::state::ReconciliationReport::try_deserialize(&mut report_account.data.as_ref())
+                .unwrap()
+        };
+
+        ctx.execute_deposit(&user, &mint, &vault, 600_000);
+        let report = read_report(&mut ctx);
+        assert_eq!(report.vault_balance, 600_000);
+        assert_eq!(report.total_deposited, 600_000);
+        assert_eq!((report.surplus, report.deficit), (0, 0));
+
+        // Clawing back out of the vault without the depositor's ledger is refused
+        let admin_transfer_ix = ctx.admin_transfer_ix(&mint, &vault, &admin_ata, 200_000, None);
+        let message = Message::new(&[admin_transfer_ix], Some(&ctx.payer.pubkey()));
+        let transaction =
+            Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+        assert_eq!(
+            result.err().map(|failure| failure.err),
+            Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(VaultCode::LedgerRequired.into())
+            ))
+        );
+
+        // With the ledger, the clawback is debited and the vault stays covered
+        ctx.execute_admin_transfer(&mint, &vault, &admin_ata, 200_000, Some(user.pubkey()));
+        assert_eq!(ctx.get_token_balance(&admin_ata), 200_000);
+        assert_eq!(ctx.get_amount_ledger(&user.pubkey()).unwrap().amount, 400_000);
+        let report = read_report(&mut ctx);
+        assert_eq!(report.vault_balance, 400_000);
+        assert_eq!(report.total_deposited, 400_000);
+        assert_eq!((report.surplus, report.deficit), (0, 0));
+
+        // A permanent-delegate transfer straight through Token-2022 bypasses the ledgers
+        let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
+            &TOKEN_2022_PROGRAM_ID,
+            &vault,
+            &mint,
+            &admin_ata,
+            &admin,
+            &[],
+            50_000,
+            6,
+        )
+        .unwrap();
+        transfer_ix
+            .accounts
+            .extend(ctx.hook_accounts(&vault, &mint, &admin_ata, &admin, 50_000));
+        let message = Message::new(&[transfer_ix], Some(&ctx.payer.pubkey()));
+        let transaction =
+            Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        ctx.program.send_transaction(transaction).unwrap();
+
+        let report = read_report(&mut ctx);
+        assert_eq!(report.vault_balance, 350_000);
+        assert_eq!(report.total_deposited, 400_000);
+        assert_eq!(report.deficit, 50_000);
+
+        msg!("✓ Deposits keep the vault fully covered");
+        msg!("✓ Clawbacks through admin_transfer debit the ledger");
+        msg!("✓ Direct permanent-delegate moves show up as a deficit");
+    }
+
+    #[test]
+    pub fn test_reward_accounting_is_pro_rata() {
+        let mut vault_config = crate::state::VaultConfig {
+            admin: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            vault_id: 0,
+            total_deposited: 0,
+            reward_mint: Pubkey::new_unique(),
+            acc_reward_per_share: 0,
+        };
+        let new_ledger = || crate::state::Amount {
+            amount: 0,
+            bump: 255,
+            lock_until: 0,
+            reward_debt: 0,
+            pending_rewards: 0,
+        };
+        let mut alice = new_ledger();
+        let mut bob = new_ledger();
+
+        // Nothing to distribute to before the first deposit
+        assert!(vault_config.distribute_rewards(1_000).is_err());
+
+        // Alice deposits 300, Bob deposits 100
+        for (ledger, amount) in [(&mut alice, 300), (&mut bob, 100)] {
+            ledger
+                .credit(amount, vault_config.acc_reward_per_share)
+                .unwrap();
+            vault_config.total_deposited += amount;
+        }
+
+        vault_config.distribute_rewards(1_000).unwrap();
+
+        // Bob joins late with another 400, which must not earn the first round
+        bob.credit(400, vault_config.acc_reward_per_share).unwrap();
+        vault_config.total_deposited += 400;
+
+        vault_config.distribute_rewards(800).unwrap();
+
+        let acc = vault_config.acc_reward_per_share;
+        alice.settle_rewards(acc).unwrap();
+        bob.settle_rewards(acc).unwrap();
+
+        // Round 1: 750 / 250, round 2 (300 vs 500 of 800): 300 / 500
+        assert_eq!(alice.pending_rewards, 1_050);
+        assert_eq!(bob.pending_rewards, 750);
+
+        msg!("✓ Rewards split pro-rata across deposits");
+    }
+
     // #[test]
     // pub fn test_deposit_with_whitelist() {
     //     let mut ctx = VaultTestContext::new();
@@ -539,8 +1749,11 @@ mod tests {
     //     assert_eq!(vault_balance, 500_000);
 
     //     // Verify amount_pda state
-    //     let amount_pda =
-    //         Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+    //     let amount_pda = Pubkey::find_program_address(
+    //         &[b"amount", ctx.vault_config.as_ref(), user.pubkey().as_ref()],
+    //         &PROGRAM_ID,
+    //     )
+    //     .0;
     //     let amount_pda_account = ctx.program.get_account(&amount_pda).unwrap();
     //     let amount_pda_data =
     //         crate::state::Amount::try_deserialize(&mut amount_pda_account.data.as_ref()).unwrap();
@@ -590,8 +1803,11 @@ mod tests {
     //     assert_eq!(vault_balance_after, 0);
 
     //     // Verify amount_pda is closed
-    //     let amount_pda =
-    //         Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+    //     let amount_pda = Pubkey::find_program_address(
+    //         &[b"amount", ctx.vault_config.as_ref(), user.pubkey().as_ref()],
+    //         &PROGRAM_ID,
+    //     )
+    //     .0;
     //     ctx.assert_account_closed(&amount_pda, "Amount PDA");
 
     //     msg!("✓ Withdraw successful with whitelist validation");
@@ -618,8 +1834,11 @@ mod tests {
 
     //     // First deposit
     //     ctx.execute_deposit(&user, &mint, &vault, 200_000);
-    //     let amount_pda =
-    //         Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+    //     let amount_pda = Pubkey::find_program_address(
+    //         &[b"amount", ctx.vault_config.as_ref(), user.pubkey().as_ref()],
+    //         &PROGRAM_ID,
+    //     )
+    //     .0;
     //     let amount_data_1 = ctx.program.get_account(&amount_pda).unwrap();
     //     let amount_1 =
     //         crate::state::Amount::try_deserialize(&mut amount_data_1.data.as_ref()).unwrap();
@@ -668,8 +1887,11 @@ mod tests {
     //     assert_eq!(vault_balance, 500_000);
 
     //     // Verify amount_pda is NOT closed (still has balance)
-    //     let amount_pda =
-    //         Pubkey::find_program_address(&[b"amount", user.pubkey().as_ref()], &PROGRAM_ID).0;
+    //     let amount_pda = Pubkey::find_program_address(
+    //         &[b"amount", ctx.vault_config.as_ref(), user.pubkey().as_ref()],
+    //         &PROGRAM_ID,
+    //     )
+    //     .0;
     //     let amount_data = ctx.program.get_account(&amount_pda).unwrap();
     //     let amount = crate::state::Amount::try_deserialize(&mut amount_data.data.as_ref()).unwrap();
     //     assert_eq!(amount.amount, 500_000);
@@ -777,7 +1999,7 @@ mod tests {
 
     //     // Admin uses permanent delegate to transfer from user1 to user2 (without user1's signature)
     //     let emergency_amount = 300_000u64;
-    //     ctx.execute_emergency_transfer(&mint, &user1_ata, &user2_ata, emergency_amount);
+    //     ctx.execute_admin_transfer(&mint, &user1_ata, &user2_ata, emergency_amount, None);
 
     //     // Verify balances after emergency transfer
     //     let user1_balance_after = ctx.get_token_balance(&user1_ata);
@@ -0,0 +1,94 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_2022::{
+    spl_token_2022::{
+        self, amount_to_ui_amount_string_trimmed,
+        extension::{
+            interest_bearing_mint::InterestBearingConfig, transfer_fee::TransferFeeConfig,
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Mint as MintState,
+    },
+    TransferChecked,
+};
+
+use crate::error::VaultCode;
+
+/// Tops up `account` so it stays rent exempt after a Token-2022 realloc
+/// (e.g. writing variable-length TokenMetadata into the mint).
+pub fn update_account_lamports_to_minimum_balance<'info>(
+    account: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let extra_lamports = Rent::get()?
+        .minimum_balance(account.data_len())
+        .saturating_sub(account.get_lamports());
+
+    if extra_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program,
+                Transfer {
+                    from: payer,
+                    to: account,
+                },
+            ),
+            extra_lamports,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// `transfer_checked` that also hands the context's remaining accounts to Token-2022,
+/// which needs them to invoke the mint's transfer hook. Anchor's version drops them.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Fee Token-2022 will withhold when `amount` is moved with `mint`,
+/// zero if the mint has no TransferFeeConfig extension.
+pub fn transfer_fee_for(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(VaultCode::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// UI amount of `amount` raw tokens of `mint`, including interest accrued so far
+/// if the mint has the InterestBearingConfig extension. Raw balances never rebase.
+pub fn ui_amount_for(mint: &AccountInfo, amount: u64) -> Result<String> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    let decimals = mint_state.base.decimals;
+
+    match mint_state.get_extension::<InterestBearingConfig>() {
+        Ok(interest_config) => Ok(interest_config
+            .amount_to_ui_amount(amount, decimals, Clock::get()?.unix_timestamp)
+            .ok_or(VaultCode::MathOverflow)?),
+        Err(_) => Ok(amount_to_ui_amount_string_trimmed(amount, decimals)),
+    }
+}
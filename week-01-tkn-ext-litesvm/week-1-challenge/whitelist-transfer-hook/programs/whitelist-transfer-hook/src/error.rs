@@ -13,4 +13,94 @@ pub enum ErrorCode {
 
     #[msg("Whitelist entry is inactive")]
     WhitelistEntryInactive,
+
+    #[msg("Signer is not the hook authority for this mint")]
+    Unauthorized,
+
+    #[msg("Mint has neither a transfer-hook authority nor a mint authority")]
+    NoHookAuthority,
+
+    #[msg("Signer is not the pending hook authority")]
+    NotPendingAuthority,
+
+    #[msg("Source account is blocked")]
+    SourceBlocked,
+
+    #[msg("Destination account is blocked")]
+    DestinationBlocked,
+
+    #[msg("Number of users does not match the number of entry accounts")]
+    BatchLengthMismatch,
+
+    #[msg("Account is not the whitelist entry PDA for this mint and user")]
+    InvalidWhitelistEntry,
+
+    #[msg("Whitelist entry is not valid yet")]
+    WhitelistEntryNotYetValid,
+
+    #[msg("Whitelist entry has expired")]
+    WhitelistEntryExpired,
+
+    #[msg("valid_until must be 0 or later than valid_from")]
+    InvalidValidityWindow,
+
+    #[msg("Whitelist entry has not expired")]
+    WhitelistEntryNotExpired,
+
+    #[msg("Transfer hook was not invoked during a token transfer")]
+    NotTransferring,
+
+    #[msg("Transfer exceeds the daily volume limit")]
+    DailyLimitExceeded,
+
+    #[msg("Transfer exceeds the weekly volume limit")]
+    WeeklyLimitExceeded,
+
+    #[msg("Transfer stats must be initialized while velocity limits are set")]
+    TransferStatsNotInitialized,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Tier is out of range")]
+    InvalidTier,
+
+    #[msg("Source tier may not send to the destination tier")]
+    CounterpartyTierNotAllowed,
+
+    #[msg("Source account is not allowed to send")]
+    SourceCannotSend,
+
+    #[msg("Destination account is not allowed to receive")]
+    DestinationCannotReceive,
+
+    #[msg("Program whitelist is full")]
+    ProgramWhitelistFull,
+
+    #[msg("Program cannot be whitelisted")]
+    InvalidProgram,
+
+    #[msg("Self-service whitelist claims are disabled")]
+    WhitelistRootNotSet,
+
+    #[msg("Merkle proof does not match the whitelist root")]
+    InvalidMerkleProof,
+
+    #[msg("Transfers of this mint are paused")]
+    TransfersPaused,
+
+    #[msg("Hook config already uses the current layout")]
+    HookConfigUpToDate,
+
+    #[msg("Transfer stats must be initialized for tiers with a weekly cumulative limit")]
+    TierTransferStatsNotInitialized,
+
+    #[msg("Transfer logging is enabled but the transfer log is missing")]
+    TransferLogRequired,
+
+    #[msg("Whitelist entry already uses the current layout")]
+    WhitelistEntryUpToDate,
+
+    #[msg("Authority is not the source account's owner, its delegate or the permanent delegate")]
+    InvalidTransferAuthority,
 }
@@ -0,0 +1,187 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry},
+};
+
+#[derive(Accounts)]
+pub struct BatchAddToWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BatchRemoveFromWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+/// Checks that `entry` is the whitelist PDA for `(mint, user)` and returns its bump
+fn verify_entry_address(mint: &Pubkey, user: &Pubkey, entry: &AccountInfo) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"whitelist", mint.as_ref(), user.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(entry.key(), expected, ErrorCode::InvalidWhitelistEntry);
+    Ok(bump)
+}
+
+impl<'info> BatchAddToWhitelist<'info> {
+    /// Same as `add_to_whitelist` for each user, with the writable entry PDAs
+    /// passed as remaining accounts in the same order as `users`.
+    /// Users that already have an entry are skipped, so a batch never re-activates
+    /// a suspended entry or resets its tier and direction flags.
+    pub fn batch_add_to_whitelist(
+        &mut self,
+        users: Vec<Pubkey>,
+        valid_from: i64,
+        valid_until: i64,
+        entries: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require_eq!(users.len(), entries.len(), ErrorCode::BatchLengthMismatch);
+        WhitelistEntry::validate_window(valid_from, valid_until)?;
+
+        let mint = self.mint.key();
+
+        for (user, entry) in users.into_iter().zip(entries) {
+            let bump = verify_entry_address(&mint, &user, entry)?;
+            if entry.owner == &crate::ID && !entry.data_is_empty() {
+                continue;
+            }
+
+            let seeds: &[&[u8]] = &[b"whitelist", mint.as_ref(), user.as_ref(), &[bump]];
+            self.create_entry_account(entry, &[seeds])?;
+
+            let whitelist_entry = WhitelistEntry {
+                user,
+                is_active: true,
+                can_send: true,
+                can_receive: true,
+                tier: 0,
+                valid_from,
+                valid_until,
+                payer: self.admin.key(),
+                bump,
+            };
+            let mut data = entry.try_borrow_mut_data()?;
+            whitelist_entry.try_serialize(&mut &mut data[..])?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the entry PDA like Anchor's `init`: `create_account` fails if the address
+    /// was pre-funded, so then only the rent shortfall is transferred before allocating
+    fn create_entry_account(
+        &self,
+        entry: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let space = 8 + WhitelistEntry::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let system_program = self.system_program.to_account_info();
+
+        if entry.lamports() == 0 {
+            return create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: self.admin.to_account_info(),
+                        to: entry.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space as u64,
+                &crate::ID,
+            );
+        }
+
+        let shortfall = rent.saturating_sub(entry.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: self.admin.to_account_info(),
+                        to: entry.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: entry.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+
+        assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: entry.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )
+    }
+}
+
+impl<'info> BatchRemoveFromWhitelist<'info> {
+    /// Same as `remove_from_whitelist` for each user, refunding rent to `admin`
+    pub fn batch_remove_from_whitelist(
+        &mut self,
+        users: Vec<Pubkey>,
+        entries: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require_eq!(users.len(), entries.len(), ErrorCode::BatchLengthMismatch);
+
+        let mint = self.mint.key();
+
+        for (user, entry) in users.into_iter().zip(entries) {
+            verify_entry_address(&mint, &user, entry)?;
+
+            // Rejects accounts that are not live WhitelistEntry PDAs of this program
+            Account::<WhitelistEntry>::try_from(entry)?.close(self.admin.to_account_info())?;
+        }
+
+        self.hook_config.whitelist_root = [0; 32];
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{BlockEntry, HookConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddToBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + BlockEntry::INIT_SPACE,
+        seeds = [b"block", mint.key().as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub block_entry: Account<'info, BlockEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveFromBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"block", mint.key().as_ref(), user.as_ref()],
+        bump = block_entry.bump,
+    )]
+    pub block_entry: Account<'info, BlockEntry>,
+}
+
+impl<'info> AddToBlocklist<'info> {
+    pub fn add_to_blocklist(&mut self, user: Pubkey, bumps: AddToBlocklistBumps) -> Result<()> {
+        self.block_entry.set_inner(BlockEntry {
+            user,
+            bump: bumps.block_entry,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RemoveFromBlocklist<'info> {
+    pub fn remove_from_blocklist(&mut self, _user: Pubkey) -> Result<()> {
+        // Closed via `close = admin`
+        Ok(())
+    }
+}
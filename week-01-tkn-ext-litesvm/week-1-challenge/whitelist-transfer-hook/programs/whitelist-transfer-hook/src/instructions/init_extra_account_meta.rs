@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 use spl_tlv_account_resolution::{
-    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+    account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed,
+    state::ExtraAccountMetaList,
 };
 
+use crate::state::{HookConfig, PolicyMode};
+
 #[derive(Accounts)]
 pub struct InitializeExtraAccountMetaList<'info> {
     #[account(mut)]
@@ -21,13 +24,42 @@ pub struct InitializeExtraAccountMetaList<'info> {
     )]
     pub extra_account_meta_list: AccountInfo<'info>,
     pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = HookConfig::LEN,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitializeExtraAccountMetaList<'info> {
+    /// The whitelist authority is taken from the mint, not from whoever pays for init
+    pub fn init_hook_config(&mut self, bumps: &InitializeExtraAccountMetaListBumps) -> Result<()> {
+        self.hook_config.set_inner(HookConfig {
+            mint: self.mint.key(),
+            authority: HookConfig::default_authority(&self.mint)?,
+            pending_authority: Pubkey::default(),
+            mode: PolicyMode::AllowList,
+            daily_limit: 0,
+            weekly_limit: 0,
+            whitelist_root: [0; 32],
+            paused: false,
+            rescue_authority: Pubkey::default(),
+            whitelist_list: Pubkey::default(),
+            transfer_log_enabled: false,
+            bump: bumps.hook_config,
+        });
+        Ok(())
+    }
+
     pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
         Ok(vec![
-            // Source owner's whitelist entry
+            // Source owner's whitelist entry, may not exist
             ExtraAccountMeta::new_with_seeds(
                 &[
                     Seed::Literal {
@@ -39,7 +71,7 @@ impl<'info> InitializeExtraAccountMetaList<'info> {
                 false, // is_signer
                 false, // is_writable
             )?,
-            // Destination owner's whitelist entry
+            // Destination owner's whitelist entry, may not exist
             ExtraAccountMeta::new_with_seeds(
                 &[
                     Seed::Literal {
@@ -55,6 +87,141 @@ impl<'info> InitializeExtraAccountMetaList<'info> {
                 false, // is_signer
                 false, // is_writable
             )?,
+            // Hook config, selects the policy mode
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"hook_config".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Source owner's block entry, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"block".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                    Seed::AccountKey { index: 3 }, // owner (source authority)
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Destination owner's block entry, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"block".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                    Seed::AccountData {
+                        account_index: 2, // destination_token
+                        data_index: 32,   // owner field offset in token account
+                        length: 32,       // pubkey length
+                    },
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Source owner's transfer stats, written by the hook
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"transfer_stats".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                    // source token account's owner, so a delegate's transfers still count
+                    // against the holder whose tokens move
+                    Seed::AccountData {
+                        account_index: 0,
+                        data_index: 32,
+                        length: 32,
+                    },
+                ],
+                false, // is_signer
+                true,  // is_writable
+            )?,
+            // Tier table, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"tier_table".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Destination owner, so the hook can see which program owns it
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 2, // destination_token
+                    data_index: 32,   // owner field offset in token account
+                },
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Program whitelist, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"program_whitelist".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Source owner's entry in the shared whitelist list, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"list_entry".to_vec(),
+                    },
+                    Seed::AccountData {
+                        account_index: 7, // hook_config
+                        data_index: HookConfig::WHITELIST_LIST_OFFSET as u8,
+                        length: 32, // pubkey length
+                    },
+                    Seed::AccountKey { index: 3 }, // owner (source authority)
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Destination owner's entry in the shared whitelist list, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"list_entry".to_vec(),
+                    },
+                    Seed::AccountData {
+                        account_index: 7, // hook_config
+                        data_index: HookConfig::WHITELIST_LIST_OFFSET as u8,
+                        length: 32, // pubkey length
+                    },
+                    Seed::AccountData {
+                        account_index: 2, // destination_token
+                        data_index: 32,   // owner field offset in token account
+                        length: 32,       // pubkey length
+                    },
+                ],
+                false, // is_signer
+                false, // is_writable
+            )?,
+            // Transfer log, appended to by the hook, may not exist
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"transfer_log".to_vec(),
+                    },
+                    Seed::AccountKey { index: 1 }, // mint
+                ],
+                false, // is_signer
+                true,  // is_writable
+            )?,
         ])
     }
 }
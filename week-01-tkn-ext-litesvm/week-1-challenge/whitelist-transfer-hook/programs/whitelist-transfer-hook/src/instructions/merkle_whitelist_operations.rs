@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry, MAX_TIERS},
+};
+
+#[derive(Accounts)]
+pub struct SetWhitelistRoot<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWhitelist<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    // `init` rather than `init_if_needed`: a suspended entry must not be
+    // re-activated by claiming it again. Removal closes the entry and clears the root
+    #[account(
+        init,
+        payer = user,
+        space = 8 + WhitelistEntry::INIT_SPACE,
+        seeds = [b"whitelist", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetWhitelistRoot<'info> {
+    /// Replacing the root only affects future claims, entries already claimed stay.
+    /// Removing a user clears it, so the next root must leave them out
+    pub fn set_whitelist_root(&mut self, root: [u8; 32]) -> Result<()> {
+        self.hook_config.whitelist_root = root;
+        Ok(())
+    }
+}
+
+impl<'info> ClaimWhitelist<'info> {
+    pub fn claim_whitelist(
+        &mut self,
+        tier: u8,
+        proof: Vec<[u8; 32]>,
+        bumps: &ClaimWhitelistBumps,
+    ) -> Result<()> {
+        let root = self.hook_config.whitelist_root;
+        require!(root != [0; 32], ErrorCode::WhitelistRootNotSet);
+        require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+        require!(
+            WhitelistEntry::verify_claim(&root, &proof, &self.user.key(), tier),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        self.whitelist_entry.set_inner(WhitelistEntry {
+            user: self.user.key(),
+            is_active: true,
+            can_send: true,
+            can_receive: true,
+            tier,
+            valid_from: 0,
+            valid_until: 0,
+            payer: self.user.key(),
+            bump: bumps.whitelist_entry,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::ErrorCode, state::HookConfig};
+
+#[derive(Accounts)]
+pub struct MigrateHookConfig<'info> {
+    /// Pays the rent for the added fields
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: a HookConfig that may predate the current layout, so it can't deserialize yet
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump,
+    )]
+    pub hook_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateHookConfig<'info> {
+    /// Permissionless: grows a HookConfig created before the latest fields were added,
+    /// the new fields read as zero, which is their default
+    pub fn migrate_hook_config(&mut self) -> Result<()> {
+        let hook_config = self.hook_config.to_account_info();
+        let old_len = hook_config.data_len();
+
+        require!(
+            hook_config.try_borrow_data()?.starts_with(HookConfig::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(old_len < HookConfig::LEN, ErrorCode::HookConfigUpToDate);
+
+        let extra_lamports = Rent::get()?
+            .minimum_balance(HookConfig::LEN)
+            .saturating_sub(hook_config.lamports());
+        if extra_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: hook_config.clone(),
+                    },
+                ),
+                extra_lamports,
+            )?;
+        }
+
+        hook_config.realloc(HookConfig::LEN, true)?;
+        HookConfig::migrate_layout(&mut hook_config.try_borrow_mut_data()?, old_len);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry},
+};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct MigrateWhitelistEntry<'info> {
+    /// Pays the rent for the added fields
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    /// CHECK: a WhitelistEntry that may predate the current layout, so it can't deserialize yet
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"whitelist", mint.key().as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateWhitelistEntry<'info> {
+    /// Permissionless: rewrites an entry created with the legacy layout.
+    /// Legacy entries were funded by the whitelist authority, which becomes their `payer`.
+    pub fn migrate_whitelist_entry(&mut self, _user: Pubkey) -> Result<()> {
+        let whitelist_entry = self.whitelist_entry.to_account_info();
+
+        require!(
+            whitelist_entry
+                .try_borrow_data()?
+                .starts_with(WhitelistEntry::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        require_eq!(
+            whitelist_entry.data_len(),
+            WhitelistEntry::LEGACY_LEN,
+            ErrorCode::WhitelistEntryUpToDate
+        );
+
+        let extra_lamports = Rent::get()?
+            .minimum_balance(WhitelistEntry::LEN)
+            .saturating_sub(whitelist_entry.lamports());
+        if extra_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: whitelist_entry.clone(),
+                    },
+                ),
+                extra_lamports,
+            )?;
+        }
+
+        whitelist_entry.realloc(WhitelistEntry::LEN, true)?;
+        let mut data = whitelist_entry.try_borrow_mut_data()?;
+        WhitelistEntry::migrate_layout(&mut data, self.hook_config.authority)
+    }
+}
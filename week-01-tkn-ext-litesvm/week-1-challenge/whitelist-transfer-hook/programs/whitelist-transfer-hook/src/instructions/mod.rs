@@ -1,7 +1,37 @@
+pub mod authority_operations;
+pub mod batch_whitelist_operations;
+pub mod blocklist_operations;
 pub mod init_extra_account_meta;
+pub mod merkle_whitelist_operations;
+pub mod migrate_hook_config;
+pub mod migrate_whitelist_entry;
+pub mod pause_operations;
+pub mod program_whitelist_operations;
+pub mod prune_expired;
+pub mod set_policy_mode;
+pub mod set_tier_limits;
 pub mod transfer_hook;
+pub mod transfer_log_operations;
+pub mod transfer_stats_operations;
+pub mod update_extra_account_meta;
+pub mod whitelist_list_operations;
 pub mod whitelist_operations;
 
+pub use authority_operations::*;
+pub use batch_whitelist_operations::*;
+pub use blocklist_operations::*;
 pub use init_extra_account_meta::*;
+pub use merkle_whitelist_operations::*;
+pub use migrate_hook_config::*;
+pub use migrate_whitelist_entry::*;
+pub use pause_operations::*;
+pub use program_whitelist_operations::*;
+pub use prune_expired::*;
+pub use set_policy_mode::*;
+pub use set_tier_limits::*;
 pub use transfer_hook::*;
+pub use transfer_log_operations::*;
+pub use transfer_stats_operations::*;
+pub use update_extra_account_meta::*;
+pub use whitelist_list_operations::*;
 pub use whitelist_operations::*;
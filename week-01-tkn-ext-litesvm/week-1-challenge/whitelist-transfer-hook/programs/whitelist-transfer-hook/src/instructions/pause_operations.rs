@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::ErrorCode, state::HookConfig};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetRescueAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+impl<'info> SetPaused<'info> {
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.hook_config.paused = paused;
+        Ok(())
+    }
+}
+
+impl<'info> SetRescueAuthority<'info> {
+    /// `Pubkey::default()` removes the exemption
+    pub fn set_rescue_authority(&mut self, rescue_authority: Pubkey) -> Result<()> {
+        self.hook_config.rescue_authority = rescue_authority;
+        Ok(())
+    }
+}
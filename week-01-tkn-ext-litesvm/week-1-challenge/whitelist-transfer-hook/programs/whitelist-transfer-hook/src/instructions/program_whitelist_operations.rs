@@ -0,0 +1,87 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, ProgramWhitelist, MAX_WHITELISTED_PROGRAMS},
+};
+
+#[derive(Accounts)]
+pub struct AddProgramToWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ProgramWhitelist::INIT_SPACE,
+        seeds = [b"program_whitelist", mint.key().as_ref()],
+        bump,
+    )]
+    pub program_whitelist: Account<'info, ProgramWhitelist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveProgramFromWhitelist<'info> {
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"program_whitelist", mint.key().as_ref()],
+        bump = program_whitelist.bump,
+    )]
+    pub program_whitelist: Account<'info, ProgramWhitelist>,
+}
+
+impl<'info> AddProgramToWhitelist<'info> {
+    pub fn add_program_to_whitelist(
+        &mut self,
+        program_id: Pubkey,
+        bumps: &AddProgramToWhitelistBumps,
+    ) -> Result<()> {
+        // Every wallet is owned by the system program, approving it would whitelist everyone
+        require_keys_neq!(program_id, system_program::ID, ErrorCode::InvalidProgram);
+
+        self.program_whitelist.mint = self.mint.key();
+        self.program_whitelist.bump = bumps.program_whitelist;
+
+        if !self.program_whitelist.contains(&program_id) {
+            require!(
+                self.program_whitelist.programs.len() < MAX_WHITELISTED_PROGRAMS,
+                ErrorCode::ProgramWhitelistFull
+            );
+            self.program_whitelist.programs.push(program_id);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> RemoveProgramFromWhitelist<'info> {
+    pub fn remove_program_from_whitelist(&mut self, program_id: Pubkey) -> Result<()> {
+        self.program_whitelist
+            .programs
+            .retain(|program| program != &program_id);
+        Ok(())
+    }
+}
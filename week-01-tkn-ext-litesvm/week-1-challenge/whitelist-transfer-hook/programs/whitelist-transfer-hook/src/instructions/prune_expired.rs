@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{WhitelistEntry, WhitelistList},
+};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct PruneExpired<'info> {
+    /// CHECK: receives the entry's rent, must match the payer recorded on it
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [b"whitelist", mint.key().as_ref(), user.as_ref()],
+        bump = whitelist_entry.bump,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct PruneExpiredListEntry<'info> {
+    /// CHECK: receives the entry's rent, must match the payer recorded on it
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"whitelist_list",
+            whitelist_list.authority.as_ref(),
+            whitelist_list.list_id.to_le_bytes().as_ref(),
+        ],
+        bump = whitelist_list.bump,
+    )]
+    pub whitelist_list: Account<'info, WhitelistList>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [b"list_entry", whitelist_list.key().as_ref(), user.as_ref()],
+        bump = list_entry.bump,
+    )]
+    pub list_entry: Account<'info, WhitelistEntry>,
+}
+
+impl<'info> PruneExpired<'info> {
+    /// Permissionless: anyone can close an entry once it has expired
+    pub fn prune_expired(&mut self, _user: Pubkey) -> Result<()> {
+        require!(
+            self.whitelist_entry.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::WhitelistEntryNotExpired
+        );
+        // Closed via `close = payer`
+        Ok(())
+    }
+}
+
+impl<'info> PruneExpiredListEntry<'info> {
+    /// Same as `prune_expired` for an entry of a shared list
+    pub fn prune_expired_list_entry(&mut self, _user: Pubkey) -> Result<()> {
+        require!(
+            self.list_entry.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::WhitelistEntryNotExpired
+        );
+        // Closed via `close = payer`
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, PolicyMode},
+};
+
+#[derive(Accounts)]
+pub struct SetPolicyMode<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+impl<'info> SetPolicyMode<'info> {
+    /// Switching modes keeps existing entries; only the ones for the active mode are consulted
+    pub fn set_policy_mode(&mut self, mode: PolicyMode) -> Result<()> {
+        self.hook_config.mode = mode;
+        Ok(())
+    }
+}
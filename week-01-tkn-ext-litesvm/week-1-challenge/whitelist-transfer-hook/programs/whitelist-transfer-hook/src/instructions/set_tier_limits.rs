@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, TierLimits, TierTable, MAX_TIERS},
+};
+
+#[derive(Accounts)]
+pub struct SetTierLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TierTable::INIT_SPACE,
+        seeds = [b"tier_table", mint.key().as_ref()],
+        bump,
+    )]
+    pub tier_table: Account<'info, TierTable>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetTierLimits<'info> {
+    /// Changes the limits of every holder in `tier` at once.
+    /// Tiers that were never set stay unrestricted.
+    pub fn set_tier_limits(
+        &mut self,
+        tier: u8,
+        limits: TierLimits,
+        bumps: &SetTierLimitsBumps,
+    ) -> Result<()> {
+        require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+
+        if self.tier_table.mint == Pubkey::default() {
+            self.tier_table.set_inner(TierTable {
+                mint: self.mint.key(),
+                tiers: [TierLimits::UNRESTRICTED; MAX_TIERS],
+                bump: bumps.tier_table,
+            });
+        }
+
+        self.tier_table.tiers[tier as usize] = limits;
+        Ok(())
+    }
+}
@@ -1,28 +1,37 @@
-use std::cell::RefMut;
+use std::cell::Ref;
 
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token_2022::spl_token_2022::{
         extension::{
+            permanent_delegate::PermanentDelegate,
             transfer_hook::TransferHookAccount, 
-            BaseStateWithExtensionsMut, 
-            PodStateWithExtensionsMut
+            BaseStateWithExtensions, 
+            PodStateWithExtensions
         }, 
         pod::PodAccount
     }, 
     token_interface::{
+        get_mint_extension_data,
         Mint, 
         TokenAccount
     }
 };
 
-use crate::{state::WhitelistEntry, error::ErrorCode};
+use solana_curve25519::edwards::{validate_edwards, PodEdwardsPoint};
+
+use crate::{
+    error::ErrorCode,
+    state::{
+        BlockEntry, HookConfig, PolicyMode, ProgramWhitelist, TierLimits, TierTable,
+        TransferLog, TransferRecord, TransferStats, WhitelistEntry,
+    },
+};
 
 #[derive(Accounts)]
 pub struct TransferHook<'info> {
     #[account(
         token::mint = mint, 
-        token::authority = owner,
     )]
     pub source_token: InterfaceAccount<'info, TokenAccount>,
 
@@ -33,7 +42,8 @@ pub struct TransferHook<'info> {
     )]
     pub destination_token: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: source token account owner, can be SystemAccount or PDA owned by another program
+    /// CHECK: the transfer's authority, the source token account's owner or delegate,
+    /// can be SystemAccount or PDA owned by another program. Checked in the handler
     pub owner: UncheckedAccount<'info>,
     
     /// CHECK: ExtraAccountMetaList Account,
@@ -43,17 +53,92 @@ pub struct TransferHook<'info> {
     )]
     pub extra_account_meta_list: UncheckedAccount<'info>,
 
+    /// CHECK: source owner's WhitelistEntry, may not exist, loaded in the handler
     #[account(
         seeds = [b"whitelist", mint.key().as_ref(), owner.key().as_ref()], 
-        bump = source_whitelist.bump,
+        bump,
     )]
-    pub source_whitelist: Account<'info, WhitelistEntry>,
+    pub source_whitelist: UncheckedAccount<'info>,
     
+    /// CHECK: destination owner's WhitelistEntry, may not exist, loaded in the handler
     #[account(
         seeds = [b"whitelist", mint.key().as_ref(), destination_token.owner.as_ref()], 
-        bump = destination_whitelist.bump,
+        bump,
     )]
-    pub destination_whitelist: Account<'info, WhitelistEntry>,
+    pub destination_whitelist: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    /// CHECK: source owner's BlockEntry, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"block", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub source_block: UncheckedAccount<'info>,
+
+    /// CHECK: destination owner's BlockEntry, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"block", mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub destination_block: UncheckedAccount<'info>,
+
+    /// CHECK: TransferStats of the source token account's owner, which differs from
+    /// `owner` when a delegate signs, only required while velocity limits are set
+    #[account(
+        mut,
+        seeds = [b"transfer_stats", mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+    )]
+    pub transfer_stats: UncheckedAccount<'info>,
+
+    /// CHECK: the mint's TierTable, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"tier_table", mint.key().as_ref()],
+        bump,
+    )]
+    pub tier_table: UncheckedAccount<'info>,
+
+    /// CHECK: destination token account owner, only its owning program is read
+    #[account(
+        address = destination_token.owner,
+    )]
+    pub destination_owner: UncheckedAccount<'info>,
+
+    /// CHECK: the mint's ProgramWhitelist, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"program_whitelist", mint.key().as_ref()],
+        bump,
+    )]
+    pub program_whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: source owner's entry in the shared list, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"list_entry", hook_config.whitelist_list.as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub source_list_entry: UncheckedAccount<'info>,
+
+    /// CHECK: destination owner's entry in the shared list, may not exist, loaded in the handler
+    #[account(
+        seeds = [b"list_entry", hook_config.whitelist_list.as_ref(), destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub destination_list_entry: UncheckedAccount<'info>,
+
+    /// CHECK: the mint's TransferLog, required once `hook_config.transfer_log_enabled`
+    /// is set, loaded in the handler. Always writable in the meta list, so the runtime
+    /// runs transfers of one mint one at a time even before the log exists.
+    #[account(
+        mut,
+        seeds = [b"transfer_log", mint.key().as_ref()],
+        bump,
+    )]
+    pub transfer_log: UncheckedAccount<'info>,
 }
 
 impl<'info> TransferHook<'info> {
@@ -61,60 +146,259 @@ impl<'info> TransferHook<'info> {
     pub fn transfer_hook(&mut self, amount: u64) -> Result<()> {
         // Fail this instruction if it is not called from within a transfer hook
         self.check_is_transferring()?;
+        self.check_authority()?;
+
+        // Exempt authorities skip the policy and velocity limits, so a rescue
+        // can't be held up by the state it is responding to. It is still logged
+        if self.hook_config.paused {
+            require!(self.is_pause_exempt(), ErrorCode::TransfersPaused);
+            return self.append_to_log(amount);
+        }
+
+        let tier_weekly_limit = match self.hook_config.mode {
+            PolicyMode::AllowList => self.check_allow_list(amount)?,
+            PolicyMode::DenyList => {
+                self.check_deny_list()?;
+                0
+            }
+        };
+
+        self.record_volume(amount, tier_weekly_limit)?;
+        self.append_to_log(amount)
+    }
 
-        // Validate source is whitelisted and active
+    /// `owner` signs for the source account as its owner, its delegate or the
+    /// mint's permanent delegate
+    fn check_authority(&self) -> Result<()> {
+        let authority = self.owner.key();
+        let delegate = Option::<Pubkey>::from(self.source_token.delegate);
         require!(
-            self.source_whitelist.is_active,
-            ErrorCode::SourceNotWhitelisted
+            authority == self.source_token.owner
+                || delegate == Some(authority)
+                || self.permanent_delegate() == Some(authority),
+            ErrorCode::InvalidTransferAuthority
         );
-        
-        // Validate destination is whitelisted and active
+        Ok(())
+    }
+
+    fn permanent_delegate(&self) -> Option<Pubkey> {
+        get_mint_extension_data::<PermanentDelegate>(&self.mint.to_account_info())
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.delegate))
+    }
+
+    /// Appends the transfer to the mint's log, skipped only while logging was never enabled
+    fn append_to_log(&self, amount: u64) -> Result<()> {
+        if !self.hook_config.transfer_log_enabled {
+            return Ok(());
+        }
+
+        let mut transfer_log = load_entry::<TransferLog>(&self.transfer_log)?
+            .ok_or(ErrorCode::TransferLogRequired)?;
+
+        let clock = Clock::get()?;
+        transfer_log.append(TransferRecord {
+            source: self.source_token.key(),
+            destination: self.destination_token.key(),
+            amount,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+        })?;
+
+        let mut data = self.transfer_log.try_borrow_mut_data()?;
+        transfer_log.try_serialize(&mut &mut data[..])
+    }
+
+    /// While paused only the permanent delegate and the rescue authority may move tokens
+    fn is_pause_exempt(&self) -> bool {
+        let authority = self.owner.key();
+        let rescue_authority = self.hook_config.rescue_authority;
+        if rescue_authority != Pubkey::default() && authority == rescue_authority {
+            return true;
+        }
+
+        self.permanent_delegate() == Some(authority)
+    }
+
+    /// Accumulates the source owner's outgoing volume and enforces the velocity limits
+    fn record_volume(&self, amount: u64, tier_weekly_limit: u64) -> Result<()> {
+        let daily_limit = self.hook_config.daily_limit;
+        let weekly_limit = self.hook_config.effective_weekly_limit(tier_weekly_limit);
+        if daily_limit == 0 && weekly_limit == 0 {
+            return Ok(());
+        }
+
+        let mut transfer_stats = match load_entry::<TransferStats>(&self.transfer_stats)? {
+            Some(transfer_stats) => transfer_stats,
+            // Name the tier as the cause when only the tier imposes a limit
+            None if daily_limit == 0 && self.hook_config.weekly_limit == 0 => {
+                return err!(ErrorCode::TierTransferStatsNotInitialized)
+            }
+            None => return err!(ErrorCode::TransferStatsNotInitialized),
+        };
+        transfer_stats.record(
+            amount,
+            Clock::get()?.unix_timestamp,
+            daily_limit,
+            weekly_limit,
+        )?;
+
+        let mut data = self.transfer_stats.try_borrow_mut_data()?;
+        transfer_stats.try_serialize(&mut &mut data[..])
+    }
+
+    /// Returns the source tier's trailing 7-day limit, enforced with the velocity limits
+    fn check_allow_list(&self, amount: u64) -> Result<u64> {
+        let program_whitelist = load_entry::<ProgramWhitelist>(&self.program_whitelist)?;
+
+        // Only destinations fall back to the program whitelist, a program-owned
+        // source still needs its own entry to send
+        let source_whitelist = resolve_entry(
+            &self.source_whitelist,
+            &self.source_list_entry,
+            &self.owner,
+            None,
+        )?
+        .ok_or(ErrorCode::SourceNotWhitelisted)?;
+        let destination_whitelist = resolve_entry(
+            &self.destination_whitelist,
+            &self.destination_list_entry,
+            &self.destination_owner,
+            program_whitelist.as_ref(),
+        )?
+        .ok_or(ErrorCode::DestinationNotWhitelisted)?;
+
+        // Suspended entries stay on chain but cannot transfer
         require!(
-            self.destination_whitelist.is_active,
-            ErrorCode::DestinationNotWhitelisted
+            source_whitelist.is_active,
+            ErrorCode::WhitelistEntryInactive
+        );
+        require!(
+            destination_whitelist.is_active,
+            ErrorCode::WhitelistEntryInactive
         );
         
-        // Check amount limits for source (if set, 0 = unlimited)
-        if self.source_whitelist.max_transfer_amount > 0 {
+        // Each side is checked for its own direction
+        require!(source_whitelist.can_send, ErrorCode::SourceCannotSend);
+        require!(
+            destination_whitelist.can_receive,
+            ErrorCode::DestinationCannotReceive
+        );
+
+        // Reject entries outside their validity window
+        let now = Clock::get()?.unix_timestamp;
+        source_whitelist.check_validity(now)?;
+        destination_whitelist.check_validity(now)?;
+
+        // Without a tier table every tier is unrestricted
+        let source_limits = match load_entry::<TierTable>(&self.tier_table)? {
+            Some(tier_table) => tier_table.limits(source_whitelist.tier),
+            None => TierLimits::UNRESTRICTED,
+        };
+
+        require!(
+            source_limits.allows_counterparty(destination_whitelist.tier),
+            ErrorCode::CounterpartyTierNotAllowed
+        );
+
+        // Check amount limits for the source tier (if set, 0 = unlimited)
+        if source_limits.max_transfer_amount > 0 {
             require!(
-                amount <= self.source_whitelist.max_transfer_amount,
+                amount <= source_limits.max_transfer_amount,
                 ErrorCode::ExceedsMaxTransferAmount
             );
         }
-        
+
         msg!(
             "Transfer validated: {} tokens from {} to {}",
             amount,
-            self.source_whitelist.user,
-            self.destination_whitelist.user
+            source_whitelist.user,
+            destination_whitelist.user
+        );
+
+        Ok(source_limits.weekly_cumulative_limit)
+    }
+
+    fn check_deny_list(&self) -> Result<()> {
+        require!(
+            load_entry::<BlockEntry>(&self.source_block)?.is_none(),
+            ErrorCode::SourceBlocked
+        );
+        require!(
+            load_entry::<BlockEntry>(&self.destination_block)?.is_none(),
+            ErrorCode::DestinationBlocked
         );
 
         Ok(())
     }
 
     /// Checks if the transfer hook is being executed during a transfer operation.
-    fn check_is_transferring(&mut self) -> Result<()> {
+    fn check_is_transferring(&self) -> Result<()> {
         // Ensure that the source token account has the transfer hook extension enabled
 
         // Get the account info of the source token account
         let source_token_info = self.source_token.to_account_info();
-        // Borrow the account data mutably
-        let mut account_data_ref: RefMut<&mut [u8]> = source_token_info.try_borrow_mut_data()?;
+        // Borrow the account data, reading the flag needs no mutable access
+        let account_data_ref: Ref<&mut [u8]> = source_token_info.try_borrow_data()?;
 
-        // Unpack the account data as a PodStateWithExtensionsMut
+        // Unpack the account data as a PodStateWithExtensions
         // This will allow us to access the extensions of the token account
-        // We use PodStateWithExtensionsMut because TokenAccount is a POD (Plain Old Data) type
-        let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(*account_data_ref)?;
+        // We use PodStateWithExtensions because TokenAccount is a POD (Plain Old Data) type
+        let account = PodStateWithExtensions::<PodAccount>::unpack(*account_data_ref)?;
         // Get the TransferHookAccount extension
         // Search for the TransferHookAccount extension in the token account
         // The returning struct has a `transferring` field that indicates if the account is in the middle of a transfer operation
-        let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
+        let account_extension = account.get_extension::<TransferHookAccount>()?;
     
         // Check if the account is in the middle of a transfer operation
-        if !bool::from(account_extension.transferring) {
-            panic!("TransferHook: Not transferring");
-        }
+        require!(
+            bool::from(account_extension.transferring),
+            ErrorCode::NotTransferring
+        );
     
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// The owner's entry for this mint if it has one, then its entry in the
+/// shared list, otherwise a stand-in entry when the owner is a PDA of a
+/// program in `program_whitelist`
+fn resolve_entry(
+    entry: &AccountInfo,
+    list_entry: &AccountInfo,
+    owner: &AccountInfo,
+    program_whitelist: Option<&ProgramWhitelist>,
+) -> Result<Option<WhitelistEntry>> {
+    if let Some(whitelist_entry) = load_entry::<WhitelistEntry>(entry)? {
+        return Ok(Some(whitelist_entry));
+    }
+    if let Some(whitelist_entry) = load_entry::<WhitelistEntry>(list_entry)? {
+        return Ok(Some(whitelist_entry));
+    }
+
+    // A keypair can `assign` its account to any program, so only accept
+    // off-curve addresses, which the program must have derived, holding state
+    if owner.data_is_empty() || is_on_curve(&owner.key()) {
+        return Ok(None);
+    }
+
+    Ok(program_whitelist
+        .filter(|program_whitelist| program_whitelist.contains(owner.owner))
+        .map(|_| WhitelistEntry::for_approved_program(owner.key())))
+}
+
+/// `Pubkey::is_on_curve` is unavailable on-chain, this goes through the curve syscall
+fn is_on_curve(address: &Pubkey) -> bool {
+    validate_edwards(&PodEdwardsPoint(address.to_bytes()))
+}
+
+/// Deserializes a PDA whose address was already checked by seeds,
+/// `None` if it was never created or has been closed.
+fn load_entry<T: AccountDeserialize>(account: &AccountInfo) -> Result<Option<T>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = account.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut data.as_ref())?))
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, TransferLog, TransferRecord, TRANSFER_LOG_CAPACITY},
+};
+
+#[derive(Accounts)]
+pub struct InitTransferLog<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TransferLog::INIT_SPACE,
+        seeds = [b"transfer_log", mint.key().as_ref()],
+        bump,
+    )]
+    pub transfer_log: Account<'info, TransferLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTransferLog<'info> {
+    /// There is no instruction to close or reset the log, it only grows through the hook
+    pub fn init_transfer_log(&mut self, bumps: &InitTransferLogBumps) -> Result<()> {
+        self.transfer_log.set_inner(TransferLog {
+            mint: self.mint.key(),
+            transfer_count: 0,
+            total_volume: 0,
+            records: [TransferRecord::default(); TRANSFER_LOG_CAPACITY],
+            bump: bumps.transfer_log,
+        });
+        self.hook_config.transfer_log_enabled = true;
+        Ok(())
+    }
+}
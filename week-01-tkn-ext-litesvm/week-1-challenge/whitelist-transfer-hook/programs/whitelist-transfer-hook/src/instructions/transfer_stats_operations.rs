@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, TransferStats, DAYS_PER_WINDOW},
+};
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct InitTransferStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TransferStats::INIT_SPACE,
+        seeds = [b"transfer_stats", mint.key().as_ref(), owner.as_ref()],
+        bump,
+    )]
+    pub transfer_stats: Account<'info, TransferStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVelocityLimits<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+impl<'info> InitTransferStats<'info> {
+    /// Permissionless: the hook can't create accounts mid-transfer, so holders
+    /// of a mint with velocity limits need their stats created up front.
+    /// `owner` is the token account owner, not whichever delegate signs.
+    pub fn init_transfer_stats(&mut self, owner: Pubkey, bumps: &InitTransferStatsBumps) -> Result<()> {
+        self.transfer_stats.set_inner(TransferStats {
+            mint: self.mint.key(),
+            owner,
+            last_day: 0,
+            daily_volumes: [0; DAYS_PER_WINDOW],
+            bump: bumps.transfer_stats,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> SetVelocityLimits<'info> {
+    /// Limits apply to each holder's outgoing volume, 0 = unlimited
+    pub fn set_velocity_limits(&mut self, daily_limit: u64, weekly_limit: u64) -> Result<()> {
+        self.hook_config.daily_limit = daily_limit;
+        self.hook_config.weekly_limit = weekly_limit;
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry, WhitelistList, MAX_TIERS},
+};
+
+#[derive(Accounts)]
+#[instruction(list_id: u64)]
+pub struct CreateWhitelistList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WhitelistList::INIT_SPACE,
+        seeds = [b"whitelist_list", authority.key().as_ref(), list_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub whitelist_list: Account<'info, WhitelistList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddToWhitelistList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"whitelist_list",
+            whitelist_list.authority.as_ref(),
+            whitelist_list.list_id.to_le_bytes().as_ref(),
+        ],
+        bump = whitelist_list.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub whitelist_list: Account<'info, WhitelistList>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + WhitelistEntry::INIT_SPACE,
+        seeds = [b"list_entry", whitelist_list.key().as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub list_entry: Account<'info, WhitelistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveFromWhitelistList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"whitelist_list",
+            whitelist_list.authority.as_ref(),
+            whitelist_list.list_id.to_le_bytes().as_ref(),
+        ],
+        bump = whitelist_list.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub whitelist_list: Account<'info, WhitelistList>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"list_entry", whitelist_list.key().as_ref(), user.as_ref()],
+        bump = list_entry.bump,
+    )]
+    pub list_entry: Account<'info, WhitelistEntry>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct UpdateWhitelistListEntry<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"whitelist_list",
+            whitelist_list.authority.as_ref(),
+            whitelist_list.list_id.to_le_bytes().as_ref(),
+        ],
+        bump = whitelist_list.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub whitelist_list: Account<'info, WhitelistList>,
+
+    #[account(
+        mut,
+        seeds = [b"list_entry", whitelist_list.key().as_ref(), user.as_ref()],
+        bump = list_entry.bump,
+    )]
+    pub list_entry: Account<'info, WhitelistEntry>,
+}
+
+#[derive(Accounts)]
+pub struct SetWhitelistList<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    /// Omitted to detach the mint from its shared list
+    pub whitelist_list: Option<Account<'info, WhitelistList>>,
+
+    /// The list's authority consents to the mint relying on its entries,
+    /// may be the same key as `authority`; not needed when detaching
+    pub list_authority: Option<Signer<'info>>,
+}
+
+impl<'info> CreateWhitelistList<'info> {
+    pub fn create_whitelist_list(
+        &mut self,
+        list_id: u64,
+        bumps: &CreateWhitelistListBumps,
+    ) -> Result<()> {
+        self.whitelist_list.set_inner(WhitelistList {
+            authority: self.authority.key(),
+            list_id,
+            bump: bumps.whitelist_list,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> AddToWhitelistList<'info> {
+    pub fn add_to_whitelist_list(
+        &mut self,
+        user: Pubkey,
+        valid_from: i64,
+        valid_until: i64,
+        bumps: &AddToWhitelistListBumps,
+    ) -> Result<()> {
+        WhitelistEntry::validate_window(valid_from, valid_until)?;
+
+        self.list_entry.set_inner(WhitelistEntry {
+            user,
+            is_active: true,
+            can_send: true,
+            can_receive: true,
+            tier: 0,
+            valid_from,
+            valid_until,
+            payer: self.authority.key(),
+            bump: bumps.list_entry,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RemoveFromWhitelistList<'info> {
+    pub fn remove_from_whitelist_list(&mut self, _user: Pubkey) -> Result<()> {
+        // Closed via `close = authority`
+        Ok(())
+    }
+}
+
+impl<'info> UpdateWhitelistListEntry<'info> {
+    pub fn update_whitelist_list_entry(
+        &mut self,
+        _user: Pubkey,
+        tier: u8,
+        is_active: bool,
+        can_send: bool,
+        can_receive: bool,
+    ) -> Result<()> {
+        require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+
+        self.list_entry.tier = tier;
+        self.list_entry.is_active = is_active;
+        self.list_entry.can_send = can_send;
+        self.list_entry.can_receive = can_receive;
+        Ok(())
+    }
+}
+
+impl<'info> SetWhitelistList<'info> {
+    /// Entries for this mint keep taking precedence over the shared list
+    pub fn set_whitelist_list(&mut self) -> Result<()> {
+        if let Some(whitelist_list) = &self.whitelist_list {
+            require!(
+                self.list_authority
+                    .as_ref()
+                    .is_some_and(|list_authority| list_authority.key() == whitelist_list.authority),
+                ErrorCode::Unauthorized
+            );
+        }
+
+        self.hook_config.whitelist_list = self
+            .whitelist_list
+            .as_ref()
+            .map_or(Pubkey::default(), |whitelist_list| whitelist_list.key());
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
-use crate::state::whitelist::WhitelistEntry;
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry, MAX_TIERS},
+};
 
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
@@ -12,7 +15,14 @@ pub struct AddToWhitelist<'info> {
     pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        init_if_needed,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        init,
         payer = admin,
         space = 8 + WhitelistEntry::INIT_SPACE,
         seeds = [b"whitelist", mint.key().as_ref(), user.as_ref()],
@@ -31,6 +41,14 @@ pub struct RemoveFromWhitelist<'info> {
 
     pub mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
     #[account(
         mut,
         close = admin,
@@ -40,12 +58,47 @@ pub struct RemoveFromWhitelist<'info> {
     pub whitelist_entry: Account<'info, WhitelistEntry>,
 }
 
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct UpdateWhitelistEntry<'info> {
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"whitelist", mint.key().as_ref(), user.as_ref()],
+        bump = whitelist_entry.bump,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+}
+
 impl<'info> AddToWhitelist<'info> {
-    pub fn add_to_whitelist(&mut self, user: Pubkey, bumps: AddToWhitelistBumps) -> Result<()> {
+    pub fn add_to_whitelist(
+        &mut self,
+        user: Pubkey,
+        valid_from: i64,
+        valid_until: i64,
+        bumps: AddToWhitelistBumps,
+    ) -> Result<()> {
+        WhitelistEntry::validate_window(valid_from, valid_until)?;
+
         self.whitelist_entry.set_inner(WhitelistEntry {
             user,
             is_active: true,
-            max_transfer_amount: 0, // 0 = unlimited
+            can_send: true,
+            can_receive: true,
+            tier: 0,
+            valid_from,
+            valid_until,
+            payer: self.admin.key(),
             bump: bumps.whitelist_entry,
         });
         Ok(())
@@ -53,8 +106,30 @@ impl<'info> AddToWhitelist<'info> {
 }
 
 impl<'info> RemoveFromWhitelist<'info> {
+    /// Closed via `close = admin`. The user may still be in the Merkle tree, so
+    /// the root is cleared until the authority sets one without them
     pub fn remove_from_whitelist(&mut self, _user: Pubkey) -> Result<()> {
-        // Closed via `close = admin`
+        self.hook_config.whitelist_root = [0; 32];
+        Ok(())
+    }
+}
+
+impl<'info> UpdateWhitelistEntry<'info> {
+    /// Suspending keeps the PDA, so its settings and rent survive until reactivation
+    pub fn update_whitelist_entry(
+        &mut self,
+        _user: Pubkey,
+        tier: u8,
+        is_active: bool,
+        can_send: bool,
+        can_receive: bool,
+    ) -> Result<()> {
+        require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+
+        self.whitelist_entry.tier = tier;
+        self.whitelist_entry.is_active = is_active;
+        self.whitelist_entry.can_send = can_send;
+        self.whitelist_entry.can_receive = can_receive;
         Ok(())
     }
 }
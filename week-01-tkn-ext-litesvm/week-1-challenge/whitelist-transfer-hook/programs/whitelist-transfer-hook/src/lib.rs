@@ -2,14 +2,18 @@
 pub mod error;
 mod instructions;
 mod state;
+#[allow(clippy::module_inception)]
+pub mod tests;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{PolicyMode, TierLimits};
 
 use spl_discriminator::SplDiscriminate;
 use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_transfer_hook_interface::instruction::{
     ExecuteInstruction, InitializeExtraAccountMetaListInstruction,
+    UpdateExtraAccountMetaListInstruction,
 };
 declare_id!("2Bc7QG4A4sxTsEhefSRBQRVuWcgJvHA5jd4FcKZ5TDxm");
 
@@ -17,14 +21,177 @@ declare_id!("2Bc7QG4A4sxTsEhefSRBQRVuWcgJvHA5jd4FcKZ5TDxm");
 pub mod whitelist_transfer_hook {
     use super::*;
 
-    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, user: Pubkey) -> Result<()> {
-        ctx.accounts.add_to_whitelist(user, ctx.bumps)
+    pub fn add_to_whitelist(
+        ctx: Context<AddToWhitelist>,
+        user: Pubkey,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        ctx.accounts.add_to_whitelist(user, valid_from, valid_until, ctx.bumps)
     }
 
     pub fn remove_from_whitelist(ctx: Context<RemoveFromWhitelist>, user: Pubkey) -> Result<()> {
         ctx.accounts.remove_from_whitelist(user)
     }
 
+    pub fn update_whitelist_entry(
+        ctx: Context<UpdateWhitelistEntry>,
+        user: Pubkey,
+        tier: u8,
+        is_active: bool,
+        can_send: bool,
+        can_receive: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_whitelist_entry(user, tier, is_active, can_send, can_receive)
+    }
+
+    pub fn batch_add_to_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchAddToWhitelist<'info>>,
+        users: Vec<Pubkey>,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .batch_add_to_whitelist(users, valid_from, valid_until, ctx.remaining_accounts)
+    }
+
+    pub fn batch_remove_from_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchRemoveFromWhitelist<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.batch_remove_from_whitelist(users, ctx.remaining_accounts)
+    }
+
+    pub fn prune_expired(ctx: Context<PruneExpired>, user: Pubkey) -> Result<()> {
+        ctx.accounts.prune_expired(user)
+    }
+
+    pub fn prune_expired_list_entry(
+        ctx: Context<PruneExpiredListEntry>,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.prune_expired_list_entry(user)
+    }
+
+    pub fn set_whitelist_root(ctx: Context<SetWhitelistRoot>, root: [u8; 32]) -> Result<()> {
+        ctx.accounts.set_whitelist_root(root)
+    }
+
+    pub fn claim_whitelist(
+        ctx: Context<ClaimWhitelist>,
+        tier: u8,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.claim_whitelist(tier, proof, &ctx.bumps)
+    }
+
+    pub fn create_whitelist_list(ctx: Context<CreateWhitelistList>, list_id: u64) -> Result<()> {
+        ctx.accounts.create_whitelist_list(list_id, &ctx.bumps)
+    }
+
+    pub fn add_to_whitelist_list(
+        ctx: Context<AddToWhitelistList>,
+        user: Pubkey,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .add_to_whitelist_list(user, valid_from, valid_until, &ctx.bumps)
+    }
+
+    pub fn remove_from_whitelist_list(
+        ctx: Context<RemoveFromWhitelistList>,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_from_whitelist_list(user)
+    }
+
+    pub fn update_whitelist_list_entry(
+        ctx: Context<UpdateWhitelistListEntry>,
+        user: Pubkey,
+        tier: u8,
+        is_active: bool,
+        can_send: bool,
+        can_receive: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_whitelist_list_entry(user, tier, is_active, can_send, can_receive)
+    }
+
+    pub fn set_whitelist_list(ctx: Context<SetWhitelistList>) -> Result<()> {
+        ctx.accounts.set_whitelist_list()
+    }
+
+    pub fn add_program_to_whitelist(
+        ctx: Context<AddProgramToWhitelist>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.add_program_to_whitelist(program_id, &ctx.bumps)
+    }
+
+    pub fn remove_program_from_whitelist(
+        ctx: Context<RemoveProgramFromWhitelist>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_program_from_whitelist(program_id)
+    }
+
+    pub fn add_to_blocklist(ctx: Context<AddToBlocklist>, user: Pubkey) -> Result<()> {
+        ctx.accounts.add_to_blocklist(user, ctx.bumps)
+    }
+
+    pub fn remove_from_blocklist(ctx: Context<RemoveFromBlocklist>, user: Pubkey) -> Result<()> {
+        ctx.accounts.remove_from_blocklist(user)
+    }
+
+    pub fn set_policy_mode(ctx: Context<SetPolicyMode>, mode: PolicyMode) -> Result<()> {
+        ctx.accounts.set_policy_mode(mode)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    pub fn set_rescue_authority(
+        ctx: Context<SetRescueAuthority>,
+        rescue_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.set_rescue_authority(rescue_authority)
+    }
+
+    pub fn init_transfer_stats(ctx: Context<InitTransferStats>, owner: Pubkey) -> Result<()> {
+        ctx.accounts.init_transfer_stats(owner, &ctx.bumps)
+    }
+
+    pub fn init_transfer_log(ctx: Context<InitTransferLog>) -> Result<()> {
+        ctx.accounts.init_transfer_log(&ctx.bumps)
+    }
+
+    pub fn set_velocity_limits(
+        ctx: Context<SetVelocityLimits>,
+        daily_limit: u64,
+        weekly_limit: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_velocity_limits(daily_limit, weekly_limit)
+    }
+
+    pub fn set_tier_limits(
+        ctx: Context<SetTierLimits>,
+        tier: u8,
+        limits: TierLimits,
+    ) -> Result<()> {
+        ctx.accounts.set_tier_limits(tier, limits, &ctx.bumps)
+    }
+
+    pub fn nominate_authority(ctx: Context<NominateAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.nominate_authority(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()
+    }
+
     #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
     pub fn initialize_transfer_hook(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
         msg!("Initializing Transfer Hook...");
@@ -41,7 +208,20 @@ pub mod whitelist_transfer_hook {
             &extra_account_metas,
         )?;
 
-        Ok(())
+        ctx.accounts.init_hook_config(&ctx.bumps)
+    }
+
+    pub fn migrate_hook_config(ctx: Context<MigrateHookConfig>) -> Result<()> {
+        ctx.accounts.migrate_hook_config()
+    }
+
+    pub fn migrate_whitelist_entry(ctx: Context<MigrateWhitelistEntry>, user: Pubkey) -> Result<()> {
+        ctx.accounts.migrate_whitelist_entry(user)
+    }
+
+    #[instruction(discriminator = UpdateExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn update_transfer_hook(ctx: Context<UpdateExtraAccountMetaList>) -> Result<()> {
+        ctx.accounts.update_extra_account_meta_list()
     }
 
     #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct BlockEntry {
+    pub user: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::transfer_hook::TransferHook,
+    token_interface::{get_mint_extension_data, Mint},
+};
+
+use crate::error::ErrorCode;
+
+/// Which entries `transfer_hook` consults for this mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PolicyMode {
+    /// Both parties need an active `WhitelistEntry`
+    AllowList,
+    /// Everyone may transfer unless they have a `BlockEntry`
+    DenyList,
+}
+
+/// New fields go right before `bump` and must read as zero by default, so
+/// `migrate_hook_config` can upgrade accounts created with an older layout
+#[account]
+#[derive(InitSpace)]
+pub struct HookConfig {
+    pub mint: Pubkey,
+    /// Only this key may edit the mint's whitelist
+    pub authority: Pubkey,
+    /// Nominated by the current authority, `Pubkey::default()` when none
+    pub pending_authority: Pubkey,
+    pub mode: PolicyMode,
+    /// Max outgoing volume per holder per day, 0 = unlimited
+    pub daily_limit: u64,
+    /// Max outgoing volume per holder over the trailing 7 days, 0 = unlimited
+    pub weekly_limit: u64,
+    /// Merkle root of the self-service whitelist, all zeroes = claims disabled
+    pub whitelist_root: [u8; 32],
+    /// Halts every transfer except those by the permanent delegate or `rescue_authority`
+    pub paused: bool,
+    /// May still move tokens while paused, `Pubkey::default()` when none
+    pub rescue_authority: Pubkey,
+    /// Shared `WhitelistList` consulted after the mint's own entries, `Pubkey::default()` when none
+    pub whitelist_list: Pubkey,
+    /// Set by `init_transfer_log`, from then on every transfer must be appended to the log
+    pub transfer_log_enabled: bool,
+    pub bump: u8,
+}
+
+impl HookConfig {
+    pub const LEN: usize = 8 + HookConfig::INIT_SPACE;
+
+    /// Byte offset of `whitelist_list`, read by the ExtraAccountMetaList seeds.
+    /// Every field before it is fixed-size, so the offset never moves.
+    pub const WHITELIST_LIST_OFFSET: usize = 8 // discriminator
+        + 32 // mint
+        + 32 // authority
+        + 32 // pending_authority
+        + 1 // mode
+        + 8 // daily_limit
+        + 8 // weekly_limit
+        + 32 // whitelist_root
+        + 1 // paused
+        + 32; // rescue_authority
+
+    /// Moves the bump, the last byte of an `old_len`-byte account, to the end of the
+    /// reallocated `data` and zeroes everything in between
+    pub fn migrate_layout(data: &mut [u8], old_len: usize) {
+        let bump = data[old_len - 1];
+        data[old_len - 1..].fill(0);
+        if let Some(last) = data.last_mut() {
+            *last = bump;
+        }
+    }
+
+    /// Mint-wide weekly limit combined with a tier's weekly cumulative limit, the stricter wins
+    pub fn effective_weekly_limit(&self, tier_weekly_limit: u64) -> u64 {
+        match (self.weekly_limit, tier_weekly_limit) {
+            (0, limit) | (limit, 0) => limit,
+            (mint_limit, tier_limit) => mint_limit.min(tier_limit),
+        }
+    }
+
+    /// The mint's transfer-hook authority, falling back to its mint authority
+    pub fn default_authority(mint: &InterfaceAccount<Mint>) -> Result<Pubkey> {
+        let hook_authority = get_mint_extension_data::<TransferHook>(&mint.to_account_info())
+            .ok()
+            .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.authority));
+
+        hook_authority
+            .or(mint.mint_authority.into())
+            .ok_or(ErrorCode::NoHookAuthority.into())
+    }
+}
@@ -1,3 +1,17 @@
+pub mod blocklist;
+pub mod hook_config;
+pub mod program_whitelist;
+pub mod tier_table;
+pub mod transfer_log;
+pub mod transfer_stats;
 pub mod whitelist;
+pub mod whitelist_list;
 
+pub use blocklist::*;
+pub use hook_config::*;
+pub use program_whitelist::*;
+pub use tier_table::*;
+pub use transfer_log::*;
+pub use transfer_stats::*;
 pub use whitelist::*;
+pub use whitelist_list::*;
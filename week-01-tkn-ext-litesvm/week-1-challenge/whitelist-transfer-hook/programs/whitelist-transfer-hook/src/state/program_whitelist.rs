@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_WHITELISTED_PROGRAMS: usize = 16;
+
+/// Programs whose PDAs (escrows, vaults and AMM pools) may receive tokens
+/// without an individual `WhitelistEntry`. Sending still needs an entry.
+///
+/// Kept as one list per mint rather than one PDA per (mint, program): the
+/// ExtraAccountMetaList can only derive seeds from account keys and data, and
+/// the program owning an account is neither, so a per-program PDA could not
+/// be resolved during a transfer.
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramWhitelist {
+    pub mint: Pubkey,
+    #[max_len(MAX_WHITELISTED_PROGRAMS)]
+    pub programs: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ProgramWhitelist {
+    pub fn contains(&self, program_id: &Pubkey) -> bool {
+        self.programs.contains(program_id)
+    }
+}
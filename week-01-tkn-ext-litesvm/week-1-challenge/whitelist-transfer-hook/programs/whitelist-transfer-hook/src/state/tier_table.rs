@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_TIERS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct TierLimits {
+    /// Max amount per transfer sent by this tier, 0 = unlimited
+    pub max_transfer_amount: u64,
+    /// Max volume sent by a holder of this tier over the trailing 7 days, 0 = unlimited.
+    /// Same window as `HookConfig::weekly_limit`, tracked in the holder's `TransferStats`,
+    /// which must exist before they can send.
+    pub weekly_cumulative_limit: u64,
+    /// Bit `i` set = holders of this tier may send to tier `i`
+    pub allowed_counterparty_tiers: u8,
+}
+
+impl TierLimits {
+    pub const UNRESTRICTED: Self = Self {
+        max_transfer_amount: 0,
+        weekly_cumulative_limit: 0,
+        allowed_counterparty_tiers: u8::MAX,
+    };
+
+    pub fn allows_counterparty(&self, tier: u8) -> bool {
+        (tier as usize) < MAX_TIERS && self.allowed_counterparty_tiers & (1 << tier) != 0
+    }
+}
+
+/// Per-mint limits for each compliance tier, indexed by `WhitelistEntry::tier`
+#[account]
+#[derive(InitSpace)]
+pub struct TierTable {
+    pub mint: Pubkey,
+    pub tiers: [TierLimits; MAX_TIERS],
+    pub bump: u8,
+}
+
+impl TierTable {
+    pub fn limits(&self, tier: u8) -> TierLimits {
+        self.tiers
+            .get(tier as usize)
+            .copied()
+            .unwrap_or(TierLimits::UNRESTRICTED)
+    }
+}
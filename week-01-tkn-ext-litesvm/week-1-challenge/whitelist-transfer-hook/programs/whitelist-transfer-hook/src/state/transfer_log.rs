@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+pub const TRANSFER_LOG_CAPACITY: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct TransferRecord {
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+/// The mint's most recent transfers, written by the hook on every transfer.
+///
+/// The ExtraAccountMetaList passes its address writable whether or not it
+/// exists, so it acts as a per-mint write lock: transfers of one mint never run
+/// in parallel. That is the cost of keeping the trail in the program itself.
+#[account]
+#[derive(InitSpace)]
+pub struct TransferLog {
+    pub mint: Pubkey,
+    /// Transfers recorded since the log was created, also locates the next slot
+    pub transfer_count: u64,
+    /// Sum of every recorded amount, wider than a u64 supply so it can't overflow in practice
+    pub total_volume: u128,
+    /// Ring buffer, record `n` lives at `n % TRANSFER_LOG_CAPACITY`
+    pub records: [TransferRecord; TRANSFER_LOG_CAPACITY],
+    pub bump: u8,
+}
+
+impl TransferLog {
+    /// Overwrites the oldest record once the buffer is full
+    pub fn append(&mut self, record: TransferRecord) -> Result<()> {
+        let index = (self.transfer_count % TRANSFER_LOG_CAPACITY as u64) as usize;
+        self.records[index] = record;
+
+        self.transfer_count = self
+            .transfer_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_volume = self
+            .total_volume
+            .checked_add(record.amount as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records from oldest to newest
+    pub fn recent(&self) -> impl Iterator<Item = &TransferRecord> {
+        let len = (self.transfer_count as usize).min(TRANSFER_LOG_CAPACITY);
+        let start = (self.transfer_count as usize).wrapping_sub(len);
+        (start..start + len).map(move |n| &self.records[n % TRANSFER_LOG_CAPACITY])
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const DAYS_PER_WINDOW: usize = 7;
+
+/// Outgoing volume of one holder of one mint, bucketed per day
+#[account]
+#[derive(InitSpace)]
+pub struct TransferStats {
+    pub mint: Pubkey,
+    /// Owner of the source token accounts, delegated transfers included
+    pub owner: Pubkey,
+    /// Day (unix time / 86_400) of the last recorded transfer
+    pub last_day: i64,
+    /// Volume per day, indexed by `day % 7`, covering the trailing week
+    pub daily_volumes: [u64; DAYS_PER_WINDOW],
+    pub bump: u8,
+}
+
+impl TransferStats {
+    /// Adds `amount` to today's bucket, rejecting it if today's volume would exceed
+    /// `daily_limit` or the trailing 7 days' would exceed `weekly_limit` (0 = unlimited)
+    pub fn record(
+        &mut self,
+        amount: u64,
+        now: i64,
+        daily_limit: u64,
+        weekly_limit: u64,
+    ) -> Result<()> {
+        // Never move backwards, buckets are only valid relative to `last_day`
+        let today = (now / SECONDS_PER_DAY).max(self.last_day);
+
+        // Clear the buckets of days that have left the window since the last transfer
+        let elapsed = today - self.last_day;
+        if elapsed >= DAYS_PER_WINDOW as i64 {
+            self.daily_volumes = [0; DAYS_PER_WINDOW];
+        } else {
+            for day in self.last_day + 1..=today {
+                self.daily_volumes[day.rem_euclid(DAYS_PER_WINDOW as i64) as usize] = 0;
+            }
+        }
+        self.last_day = today;
+
+        let bucket = today.rem_euclid(DAYS_PER_WINDOW as i64) as usize;
+
+        let daily_volume = self.daily_volumes[bucket]
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            daily_limit == 0 || daily_volume <= daily_limit,
+            ErrorCode::DailyLimitExceeded
+        );
+
+        let weekly_volume = self
+            .daily_volumes
+            .iter()
+            .try_fold(amount, |total, volume| total.checked_add(*volume))
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            weekly_limit == 0 || weekly_volume <= weekly_limit,
+            ErrorCode::WeeklyLimitExceeded
+        );
+
+        self.daily_volumes[bucket] = daily_volume;
+
+        Ok(())
+    }
+}
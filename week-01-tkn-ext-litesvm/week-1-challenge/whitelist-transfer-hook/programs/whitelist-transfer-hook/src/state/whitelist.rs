@@ -1,10 +1,116 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
 
+use crate::error::ErrorCode;
+
+/// Entries created before direction flags, tiers and validity windows have
+/// a different layout, `migrate_whitelist_entry` rewrites them
 #[account]
 #[derive(InitSpace)]
 pub struct WhitelistEntry {
     pub user: Pubkey,
     pub is_active: bool,
-    pub max_transfer_amount: u64,
+    /// May appear as the source of a transfer
+    pub can_send: bool,
+    /// May appear as the destination of a transfer
+    pub can_receive: bool,
+    /// Compliance tier, limits are looked up in the mint's `TierTable`
+    pub tier: u8,
+    /// Unix timestamp from which the entry is accepted
+    pub valid_from: i64,
+    /// Unix timestamp from which the entry is rejected, 0 = never expires
+    pub valid_until: i64,
+    /// Funded the entry, receives the rent back when it is pruned
+    pub payer: Pubkey,
     pub bump: u8,
 }
+
+/// Layout of entries created before direction flags, tiers and validity windows
+#[derive(AnchorDeserialize)]
+struct LegacyWhitelistEntry {
+    user: Pubkey,
+    is_active: bool,
+    max_transfer_amount: u64,
+    bump: u8,
+}
+
+impl WhitelistEntry {
+    pub const LEN: usize = 8 + WhitelistEntry::INIT_SPACE;
+    /// Discriminator plus the legacy fields
+    pub const LEGACY_LEN: usize = 8 + 32 + 1 + 8 + 1;
+
+    /// Rewrites a legacy entry in `data`, already reallocated to `LEN`.
+    /// Per-entry caps moved to tiers, so a capped entry comes back suspended
+    /// until the authority assigns it a tier.
+    pub fn migrate_layout(data: &mut [u8], payer: Pubkey) -> Result<()> {
+        let legacy = LegacyWhitelistEntry::try_from_slice(&data[8..Self::LEGACY_LEN])
+            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
+
+        let whitelist_entry = Self {
+            user: legacy.user,
+            is_active: legacy.is_active && legacy.max_transfer_amount == 0,
+            can_send: true,
+            can_receive: true,
+            tier: 0,
+            valid_from: 0,
+            valid_until: 0,
+            payer,
+            bump: legacy.bump,
+        };
+        whitelist_entry.try_serialize(&mut &mut data[..])
+    }
+
+    /// Stand-in entry for an account owned by a whitelisted program:
+    /// active in both directions, tier 0 and never expiring
+    pub fn for_approved_program(user: Pubkey) -> Self {
+        Self {
+            user,
+            is_active: true,
+            can_send: true,
+            can_receive: true,
+            tier: 0,
+            valid_from: 0,
+            valid_until: 0,
+            payer: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    /// Leaf committed to by the hook config's `whitelist_root`, prefixed so a
+    /// leaf can never be passed off as an inner node
+    pub fn claim_leaf(user: &Pubkey, tier: u8) -> [u8; 32] {
+        hashv(&[&[0], user.as_ref(), &[tier]]).to_bytes()
+    }
+
+    /// Sibling pairs are hashed in sorted order, so proofs carry no left/right flags
+    pub fn verify_claim(root: &[u8; 32], proof: &[[u8; 32]], user: &Pubkey, tier: u8) -> bool {
+        let computed = proof
+            .iter()
+            .fold(Self::claim_leaf(user, tier), |node, sibling| {
+                let (left, right) = if node <= *sibling {
+                    (node, *sibling)
+                } else {
+                    (*sibling, node)
+                };
+                hashv(&[&[1], &left, &right]).to_bytes()
+            });
+        computed == *root
+    }
+
+    pub fn validate_window(valid_from: i64, valid_until: i64) -> Result<()> {
+        require!(
+            valid_until == 0 || valid_until > valid_from,
+            ErrorCode::InvalidValidityWindow
+        );
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.valid_until != 0 && now >= self.valid_until
+    }
+
+    pub fn check_validity(&self, now: i64) -> Result<()> {
+        require!(now >= self.valid_from, ErrorCode::WhitelistEntryNotYetValid);
+        require!(!self.is_expired(now), ErrorCode::WhitelistEntryExpired);
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A whitelist shared by every mint whose `HookConfig` points at it.
+///
+/// Its entries are ordinary `WhitelistEntry` accounts seeded by
+/// `[b"list_entry", list, user]`, so one approval covers all of those mints.
+#[account]
+#[derive(InitSpace)]
+pub struct WhitelistList {
+    /// Only this key may edit the list's entries
+    pub authority: Pubkey,
+    /// Distinguishes the lists of one authority
+    pub list_id: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,1616 @@
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            error::ErrorCode,
+            instructions::InitializeExtraAccountMetaList,
+            state::{PolicyMode, TierLimits},
+        },
+        anchor_lang::{
+            prelude::{msg, Clock},
+            solana_program::system_instruction,
+            AccountDeserialize, Discriminator, InstructionData, ToAccountMetas,
+        },
+        anchor_spl::{
+            associated_token::spl_associated_token_account,
+            token_2022::spl_token_2022::{
+                self,
+                extension::{transfer_hook, ExtensionType},
+                state::Mint as SplMint,
+            },
+        },
+        litesvm::{types::TransactionMetadata, LiteSVM},
+        solana_instruction::{error::InstructionError, AccountMeta, Instruction},
+        solana_keypair::Keypair,
+        solana_message::Message,
+        solana_native_token::LAMPORTS_PER_SOL,
+        solana_pubkey::Pubkey,
+        solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+        solana_signer::Signer,
+        solana_transaction::Transaction,
+        solana_transaction_error::TransactionError,
+        spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+        spl_transfer_hook_interface::instruction::ExecuteInstruction,
+        std::future::Future,
+    };
+
+    static PROGRAM_ID: Pubkey = crate::ID;
+    static TOKEN_2022_PROGRAM_ID: Pubkey =
+        solana_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+    fn setup() -> (LiteSVM, Keypair) {
+        let mut program = LiteSVM::new();
+        let payer = Keypair::new();
+
+        program
+            .airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("Failed to airdrop SOL to payer");
+
+        program
+            .add_program_from_file(PROGRAM_ID, "../../target/deploy/whitelist_transfer_hook.so")
+            .expect("Failed to load transfer hook program");
+
+        (program, payer)
+    }
+
+    fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+    }
+
+    fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            owner,
+            mint,
+            &TOKEN_2022_PROGRAM_ID,
+        )
+    }
+
+    /// What a transfer rejected by this hook fails with
+    fn hook_error(code: ErrorCode) -> Result<(), TransactionError> {
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code.into()),
+        ))
+    }
+
+    /// Polls a future that never waits, account fetches from LiteSVM resolve immediately
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        match std::pin::pin!(future).poll(&mut context) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("account fetch should resolve immediately"),
+        }
+    }
+
+    struct HookTestContext {
+        program: LiteSVM,
+        payer: Keypair,
+    }
+
+    impl HookTestContext {
+        fn new() -> Self {
+            let (program, payer) = setup();
+            Self { program, payer }
+        }
+
+        fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) {
+            let tx = self.try_send(instructions, signers).unwrap();
+
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn try_send(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<TransactionMetadata, TransactionError> {
+            // Repeating a transaction would reuse its signature, so each one gets a new blockhash
+            self.program.expire_blockhash();
+
+            let message = Message::new(instructions, Some(&self.payer.pubkey()));
+            let mut all_signers = vec![&self.payer];
+            all_signers.extend_from_slice(signers);
+            let transaction =
+                Transaction::new(&all_signers, message, self.program.latest_blockhash());
+            self.program
+                .send_transaction(transaction)
+                .map_err(|failure| failure.err)
+        }
+
+        /// Creates a 9-decimal mint whose transfer hook points at this program,
+        /// with the payer as its permanent delegate
+        fn execute_create_hooked_mint(&mut self) -> Pubkey {
+            let mint = Keypair::new();
+            let space = ExtensionType::try_calculate_account_len::<SplMint>(&[
+                ExtensionType::TransferHook,
+                ExtensionType::PermanentDelegate,
+            ])
+            .unwrap();
+
+            let instructions = [
+                system_instruction::create_account(
+                    &self.payer.pubkey(),
+                    &mint.pubkey(),
+                    self.program.minimum_balance_for_rent_exemption(space),
+                    space as u64,
+                    &TOKEN_2022_PROGRAM_ID,
+                ),
+                transfer_hook::instruction::initialize(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    Some(self.payer.pubkey()),
+                    Some(PROGRAM_ID),
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_permanent_delegate(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &self.payer.pubkey(),
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_mint2(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &self.payer.pubkey(),
+                    None,
+                    9,
+                )
+                .unwrap(),
+            ];
+
+            self.send(&instructions, &[&mint]);
+            msg!("Hooked mint created: {}", mint.pubkey());
+
+            mint.pubkey()
+        }
+
+        fn execute_initialize_transfer_hook(&mut self, mint: &Pubkey) {
+            let initialize_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::InitializeExtraAccountMetaList {
+                    payer: self.payer.pubkey(),
+                    extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::InitializeTransferHook {}.data(),
+            };
+
+            self.send(&[initialize_ix], &[]);
+            msg!("ExtraAccountMetaList initialized");
+        }
+
+        fn try_update_extra_account_meta_list(
+            &mut self,
+            mint: &Pubkey,
+            authority: &Keypair,
+        ) -> Result<(), TransactionError> {
+            let update_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::UpdateExtraAccountMetaList {
+                    extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                    mint: *mint,
+                    authority: authority.pubkey(),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::UpdateTransferHook {}.data(),
+            };
+
+            self.try_send(&[update_ix], &[authority]).map(|_| ())
+        }
+
+        /// Overwrites the mint's meta list with `metas`, funded at the rent minimum
+        fn set_extra_account_metas(&mut self, mint: &Pubkey, metas: &[ExtraAccountMeta]) {
+            let meta_list = pda(&[b"extra-account-metas", mint.as_ref()]);
+            let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+            ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, metas).unwrap();
+
+            let mut account = self.program.get_account(&meta_list).unwrap();
+            account.lamports = self.program.minimum_balance_for_rent_exemption(data.len());
+            account.data = data;
+            self.program.set_account(meta_list, account).unwrap();
+        }
+
+        fn execute_create_ata(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+            let create_ata_ix =
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &self.payer.pubkey(),
+                    owner,
+                    mint,
+                    &TOKEN_2022_PROGRAM_ID,
+                );
+
+            self.send(&[create_ata_ix], &[]);
+
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                owner,
+                mint,
+                &TOKEN_2022_PROGRAM_ID,
+            )
+        }
+        fn execute_mint_to(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+            let mint_to_ix = spl_token_2022::instruction::mint_to(
+                &TOKEN_2022_PROGRAM_ID,
+                mint,
+                &ata(owner, mint),
+                &self.payer.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap();
+
+            self.send(&[mint_to_ix], &[]);
+        }
+
+        /// Hooked mint with the meta list initialized and an ATA holding `amount` for each owner
+        fn setup_hooked_mint(&mut self, owners: &[&Pubkey], amount: u64) -> Pubkey {
+            let mint = self.execute_create_hooked_mint();
+            self.execute_initialize_transfer_hook(&mint);
+
+            for owner in owners {
+                self.execute_create_ata(&mint, owner);
+                if amount > 0 {
+                    self.execute_mint_to(&mint, owner, amount);
+                }
+            }
+
+            mint
+        }
+
+        /// Token-2022 transfer between ATAs, with the hook's extra accounts
+        /// resolved from the ExtraAccountMetaList the way a client would
+        fn try_transfer(
+            &mut self,
+            mint: &Pubkey,
+            owner: &Keypair,
+            recipient: &Pubkey,
+            amount: u64,
+        ) -> Result<(), TransactionError> {
+            self.try_transfer_from(mint, &owner.pubkey(), owner, recipient, amount)
+        }
+
+        /// Transfers out of `holder`'s token account, signed by `authority` as owner or delegate
+        fn try_transfer_from(
+            &mut self,
+            mint: &Pubkey,
+            holder: &Pubkey,
+            authority: &Keypair,
+            recipient: &Pubkey,
+            amount: u64,
+        ) -> Result<(), TransactionError> {
+            let transfer_ix = block_on(
+                spl_token_2022::offchain::create_transfer_checked_instruction_with_extra_metas(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &ata(holder, mint),
+                    mint,
+                    &ata(recipient, mint),
+                    &authority.pubkey(),
+                    &[],
+                    amount,
+                    9,
+                    |address| {
+                        std::future::ready(Ok(self
+                            .program
+                            .get_account(&address)
+                            .map(|account| account.data)))
+                    },
+                ),
+            )
+            .unwrap();
+
+            self.try_send(&[transfer_ix], &[authority]).map(|_| ())
+        }
+
+        fn add_to_whitelist_ix(
+            &self,
+            mint: &Pubkey,
+            user: &Pubkey,
+            valid_from: i64,
+            valid_until: i64,
+        ) -> Instruction {
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::AddToWhitelist {
+                    admin: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_entry: pda(&[b"whitelist", mint.as_ref(), user.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::AddToWhitelist {
+                    user: *user,
+                    valid_from,
+                    valid_until,
+                }
+                .data(),
+            }
+        }
+
+        fn execute_add_to_whitelist(
+            &mut self,
+            mint: &Pubkey,
+            user: &Pubkey,
+            valid_from: i64,
+            valid_until: i64,
+        ) -> Pubkey {
+            let whitelist_entry = pda(&[b"whitelist", mint.as_ref(), user.as_ref()]);
+
+            let add_to_whitelist_ix = self.add_to_whitelist_ix(mint, user, valid_from, valid_until);
+            self.send(&[add_to_whitelist_ix], &[]);
+            msg!("Whitelisted: {}", user);
+
+            whitelist_entry
+        }
+
+        fn execute_update_whitelist_entry(
+            &mut self,
+            mint: &Pubkey,
+            user: &Pubkey,
+            tier: u8,
+            is_active: bool,
+            can_send: bool,
+            can_receive: bool,
+        ) {
+            let update_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::UpdateWhitelistEntry {
+                    admin: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_entry: pda(&[b"whitelist", mint.as_ref(), user.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::UpdateWhitelistEntry {
+                    user: *user,
+                    tier,
+                    is_active,
+                    can_send,
+                    can_receive,
+                }
+                .data(),
+            };
+
+            self.send(&[update_ix], &[]);
+        }
+
+        fn get_whitelist_entry(
+            &self,
+            mint: &Pubkey,
+            user: &Pubkey,
+        ) -> Option<crate::state::WhitelistEntry> {
+            let entry = pda(&[b"whitelist", mint.as_ref(), user.as_ref()]);
+            let account = self.program.get_account(&entry)?;
+            crate::state::WhitelistEntry::try_deserialize(&mut account.data.as_ref()).ok()
+        }
+
+        /// Batch instruction with the entry PDAs of `users` as remaining accounts
+        fn batch_whitelist_ix(&self, mint: &Pubkey, users: &[Pubkey], add: bool) -> Instruction {
+            let admin = self.payer.pubkey();
+            let hook_config = pda(&[b"hook_config", mint.as_ref()]);
+
+            let (mut accounts, data) = if add {
+                (
+                    crate::accounts::BatchAddToWhitelist {
+                        admin,
+                        mint: *mint,
+                        hook_config,
+                        system_program: SYSTEM_PROGRAM_ID,
+                    }
+                    .to_account_metas(None),
+                    crate::instruction::BatchAddToWhitelist {
+                        users: users.to_vec(),
+                        valid_from: 0,
+                        valid_until: 0,
+                    }
+                    .data(),
+                )
+            } else {
+                (
+                    crate::accounts::BatchRemoveFromWhitelist {
+                        admin,
+                        mint: *mint,
+                        hook_config,
+                    }
+                    .to_account_metas(None),
+                    crate::instruction::BatchRemoveFromWhitelist {
+                        users: users.to_vec(),
+                    }
+                    .data(),
+                )
+            };
+            accounts.extend(users.iter().map(|user| {
+                AccountMeta::new(pda(&[b"whitelist", mint.as_ref(), user.as_ref()]), false)
+            }));
+
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data,
+            }
+        }
+
+        fn warp_to(&mut self, unix_timestamp: i64) {
+            let mut clock = self.program.get_sysvar::<Clock>();
+            clock.unix_timestamp = unix_timestamp;
+            self.program.set_sysvar::<Clock>(&clock);
+        }
+
+        fn now(&self) -> i64 {
+            self.program.get_sysvar::<Clock>().unix_timestamp
+        }
+
+        fn try_prune_expired(
+            &mut self,
+            mint: &Pubkey,
+            user: &Pubkey,
+        ) -> Result<(), TransactionError> {
+            let prune_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::PruneExpired {
+                    payer: self.payer.pubkey(),
+                    mint: *mint,
+                    whitelist_entry: pda(&[b"whitelist", mint.as_ref(), user.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::PruneExpired { user: *user }.data(),
+            };
+
+            self.try_send(&[prune_ix], &[]).map(|_| ())
+        }
+
+        fn execute_set_velocity_limits(
+            &mut self,
+            mint: &Pubkey,
+            daily_limit: u64,
+            weekly_limit: u64,
+        ) {
+            let limits_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetVelocityLimits {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetVelocityLimits {
+                    daily_limit,
+                    weekly_limit,
+                }
+                .data(),
+            };
+
+            self.send(&[limits_ix], &[]);
+        }
+
+        fn execute_init_transfer_stats(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+            let transfer_stats = pda(&[b"transfer_stats", mint.as_ref(), owner.as_ref()]);
+
+            let init_stats_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::InitTransferStats {
+                    payer: self.payer.pubkey(),
+                    mint: *mint,
+                    transfer_stats,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::InitTransferStats { owner: *owner }.data(),
+            };
+
+            self.send(&[init_stats_ix], &[]);
+            transfer_stats
+        }
+
+        fn execute_set_tier_limits(&mut self, mint: &Pubkey, tier: u8, limits: TierLimits) {
+            let tier_limits_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetTierLimits {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    tier_table: pda(&[b"tier_table", mint.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetTierLimits { tier, limits }.data(),
+            };
+
+            self.send(&[tier_limits_ix], &[]);
+        }
+
+        fn execute_add_program_to_whitelist(&mut self, mint: &Pubkey, program_id: &Pubkey) {
+            let add_program_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::AddProgramToWhitelist {
+                    admin: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    program_whitelist: pda(&[b"program_whitelist", mint.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::AddProgramToWhitelist {
+                    program_id: *program_id,
+                }
+                .data(),
+            };
+
+            self.send(&[add_program_ix], &[]);
+        }
+
+        /// Gives `address` some state owned by `program_id`, as if the program had created it
+        fn set_program_account(&mut self, address: &Pubkey, program_id: &Pubkey) {
+            self.program.airdrop(address, LAMPORTS_PER_SOL).unwrap();
+
+            let mut account = self.program.get_account(address).unwrap();
+            account.owner = *program_id;
+            account.data = vec![1; 8];
+            self.program.set_account(*address, account).unwrap();
+        }
+
+        fn execute_remove_from_whitelist(&mut self, mint: &Pubkey, user: &Pubkey) {
+            let remove_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::RemoveFromWhitelist {
+                    admin: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_entry: pda(&[b"whitelist", mint.as_ref(), user.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::RemoveFromWhitelist { user: *user }.data(),
+            };
+
+            self.send(&[remove_ix], &[]);
+        }
+
+        fn execute_set_whitelist_root(&mut self, mint: &Pubkey, root: [u8; 32]) {
+            let set_root_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetWhitelistRoot {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetWhitelistRoot { root }.data(),
+            };
+
+            self.send(&[set_root_ix], &[]);
+        }
+
+        fn try_claim_whitelist(
+            &mut self,
+            mint: &Pubkey,
+            user: &Keypair,
+            tier: u8,
+            proof: Vec<[u8; 32]>,
+        ) -> Result<(), TransactionError> {
+            let claim_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::ClaimWhitelist {
+                    user: user.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_entry: pda(&[b"whitelist", mint.as_ref(), user.pubkey().as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::ClaimWhitelist { tier, proof }.data(),
+            };
+
+            self.try_send(&[claim_ix], &[user]).map(|_| ())
+        }
+
+        fn execute_set_paused(&mut self, mint: &Pubkey, paused: bool) {
+            let set_paused_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetPaused {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetPaused { paused }.data(),
+            };
+
+            self.send(&[set_paused_ix], &[]);
+        }
+
+        fn execute_set_rescue_authority(&mut self, mint: &Pubkey, rescue_authority: &Pubkey) {
+            let set_rescue_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetRescueAuthority {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetRescueAuthority {
+                    rescue_authority: *rescue_authority,
+                }
+                .data(),
+            };
+
+            self.send(&[set_rescue_ix], &[]);
+        }
+
+        fn execute_create_whitelist_list(&mut self, authority: &Keypair, list_id: u64) -> Pubkey {
+            let whitelist_list = pda(&[
+                b"whitelist_list",
+                authority.pubkey().as_ref(),
+                list_id.to_le_bytes().as_ref(),
+            ]);
+
+            let create_list_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::CreateWhitelistList {
+                    authority: authority.pubkey(),
+                    whitelist_list,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::CreateWhitelistList { list_id }.data(),
+            };
+
+            self.send(&[create_list_ix], &[authority]);
+            whitelist_list
+        }
+
+        fn execute_add_to_whitelist_list(
+            &mut self,
+            whitelist_list: &Pubkey,
+            authority: &Keypair,
+            user: &Pubkey,
+            valid_until: i64,
+        ) {
+            let add_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::AddToWhitelistList {
+                    authority: authority.pubkey(),
+                    whitelist_list: *whitelist_list,
+                    list_entry: pda(&[b"list_entry", whitelist_list.as_ref(), user.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::AddToWhitelistList {
+                    user: *user,
+                    valid_from: 0,
+                    valid_until,
+                }
+                .data(),
+            };
+
+            self.send(&[add_ix], &[authority]);
+        }
+
+        fn try_set_whitelist_list(
+            &mut self,
+            mint: &Pubkey,
+            whitelist_list: &Pubkey,
+            list_authority: Option<&Keypair>,
+        ) -> Result<(), TransactionError> {
+            let set_list_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetWhitelistList {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_list: Some(*whitelist_list),
+                    list_authority: list_authority.map(|list_authority| list_authority.pubkey()),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetWhitelistList {}.data(),
+            };
+
+            let signers: Vec<&Keypair> = list_authority.into_iter().collect();
+            self.try_send(&[set_list_ix], &signers).map(|_| ())
+        }
+
+        fn try_prune_expired_list_entry(
+            &mut self,
+            whitelist_list: &Pubkey,
+            payer: &Pubkey,
+            user: &Pubkey,
+        ) -> Result<(), TransactionError> {
+            let prune_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::PruneExpiredListEntry {
+                    payer: *payer,
+                    whitelist_list: *whitelist_list,
+                    list_entry: pda(&[b"list_entry", whitelist_list.as_ref(), user.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::PruneExpiredListEntry { user: *user }.data(),
+            };
+
+            self.try_send(&[prune_ix], &[]).map(|_| ())
+        }
+
+        fn execute_init_transfer_log(&mut self, mint: &Pubkey) -> Pubkey {
+            let transfer_log = pda(&[b"transfer_log", mint.as_ref()]);
+
+            let init_log_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::InitTransferLog {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    transfer_log,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::InitTransferLog {}.data(),
+            };
+
+            self.send(&[init_log_ix], &[]);
+            transfer_log
+        }
+
+        fn execute_set_policy_mode(&mut self, mint: &Pubkey, mode: PolicyMode) {
+            let set_policy_mode_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::SetPolicyMode {
+                    authority: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: crate::instruction::SetPolicyMode { mode }.data(),
+            };
+
+            self.send(&[set_policy_mode_ix], &[]);
+        }
+
+        fn execute_set_blocked(&mut self, mint: &Pubkey, user: &Pubkey, blocked: bool) {
+            let block_entry = pda(&[b"block", mint.as_ref(), user.as_ref()]);
+            let hook_config = pda(&[b"hook_config", mint.as_ref()]);
+
+            let blocklist_ix = if blocked {
+                Instruction {
+                    program_id: PROGRAM_ID,
+                    accounts: crate::accounts::AddToBlocklist {
+                        admin: self.payer.pubkey(),
+                        mint: *mint,
+                        hook_config,
+                        block_entry,
+                        system_program: SYSTEM_PROGRAM_ID,
+                    }
+                    .to_account_metas(None),
+                    data: crate::instruction::AddToBlocklist { user: *user }.data(),
+                }
+            } else {
+                Instruction {
+                    program_id: PROGRAM_ID,
+                    accounts: crate::accounts::RemoveFromBlocklist {
+                        admin: self.payer.pubkey(),
+                        mint: *mint,
+                        hook_config,
+                        block_entry,
+                    }
+                    .to_account_metas(None),
+                    data: crate::instruction::RemoveFromBlocklist { user: *user }.data(),
+                }
+            };
+
+            self.send(&[blocklist_ix], &[]);
+        }
+    }
+
+    #[test]
+    pub fn test_transfer_hook_outside_transfer_fails() {
+        let mut ctx = HookTestContext::new();
+        let mint = ctx.execute_create_hooked_mint();
+        ctx.execute_initialize_transfer_hook(&mint);
+
+        let owner = ctx.payer.pubkey();
+        let recipient = Pubkey::new_unique();
+        let source_token = ctx.execute_create_ata(&mint, &owner);
+        let destination_token = ctx.execute_create_ata(&mint, &recipient);
+
+        // Invoke the hook directly instead of through a Token-2022 transfer
+        let transfer_hook_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TransferHook {
+                source_token,
+                mint,
+                destination_token,
+                owner,
+                extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                source_whitelist: pda(&[b"whitelist", mint.as_ref(), owner.as_ref()]),
+                destination_whitelist: pda(&[b"whitelist", mint.as_ref(), recipient.as_ref()]),
+                hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                source_block: pda(&[b"block", mint.as_ref(), owner.as_ref()]),
+                destination_block: pda(&[b"block", mint.as_ref(), recipient.as_ref()]),
+                transfer_stats: pda(&[b"transfer_stats", mint.as_ref(), owner.as_ref()]),
+                tier_table: pda(&[b"tier_table", mint.as_ref()]),
+                destination_owner: recipient,
+                program_whitelist: pda(&[b"program_whitelist", mint.as_ref()]),
+                // No shared list is set, so these derive from `Pubkey::default()`
+                source_list_entry: pda(&[b"list_entry", Pubkey::default().as_ref(), owner.as_ref()]),
+                destination_list_entry: pda(&[
+                    b"list_entry",
+                    Pubkey::default().as_ref(),
+                    recipient.as_ref(),
+                ]),
+                transfer_log: pda(&[b"transfer_log", mint.as_ref()]),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::TransferHook { amount: 1 }.data(),
+        };
+
+        let message = Message::new(&[transfer_hook_ix], Some(&ctx.payer.pubkey()));
+        let transaction = Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+
+        assert_eq!(
+            result.err().unwrap().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::NotTransferring.into())
+            )
+        );
+
+        msg!("✓ Direct transfer_hook call rejected with NotTransferring");
+    }
+
+    #[test]
+    pub fn test_transfer_stats_rolling_window() {
+        use crate::state::{TransferStats, SECONDS_PER_DAY};
+
+        let mut stats = TransferStats {
+            mint: Pubkey::default(),
+            owner: Pubkey::default(),
+            last_day: 0,
+            daily_volumes: [0; 7],
+            bump: 0,
+        };
+        let day = |n: i64| n * SECONDS_PER_DAY + 60;
+
+        // 100/day, 250/week
+        stats.record(100, day(10), 100, 250).unwrap();
+        assert!(stats.record(1, day(10), 100, 250).is_err());
+
+        stats.record(100, day(11), 100, 250).unwrap();
+        stats.record(50, day(12), 100, 250).unwrap();
+        // Day 13 would put the trailing week at 251
+        assert!(stats.record(1, day(13), 100, 250).is_err());
+
+        // Day 10 leaves the window on day 17
+        stats.record(100, day(17), 100, 250).unwrap();
+        assert_eq!(stats.daily_volumes.iter().sum::<u64>(), 250);
+
+        // A gap longer than the window clears everything
+        stats.record(100, day(40), 100, 250).unwrap();
+        assert_eq!(stats.daily_volumes.iter().sum::<u64>(), 100);
+
+        msg!("✓ Daily and weekly volume limits enforced over a rolling week");
+    }
+
+    #[test]
+    pub fn test_whitelist_claim_proof() {
+        use {crate::state::WhitelistEntry, anchor_lang::solana_program::hash::hashv};
+
+        let node = |a: [u8; 32], b: [u8; 32]| {
+            let (left, right) = if a <= b { (a, b) } else { (b, a) };
+            hashv(&[&[1], &left, &right]).to_bytes()
+        };
+
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let carol = Pubkey::new_unique();
+        let leaf_a = WhitelistEntry::claim_leaf(&alice, 0);
+        let leaf_b = WhitelistEntry::claim_leaf(&bob, 2);
+        let leaf_c = WhitelistEntry::claim_leaf(&carol, 1);
+
+        // Three leaves, carol is promoted to the second level unpaired
+        let ab = node(leaf_a, leaf_b);
+        let root = node(ab, leaf_c);
+
+        assert!(WhitelistEntry::verify_claim(&root, &[leaf_b, leaf_c], &alice, 0));
+        assert!(WhitelistEntry::verify_claim(&root, &[leaf_a, leaf_c], &bob, 2));
+        assert!(WhitelistEntry::verify_claim(&root, &[ab], &carol, 1));
+
+        // Claiming a different tier than the one committed to fails
+        assert!(!WhitelistEntry::verify_claim(&root, &[leaf_a, leaf_c], &bob, 0));
+        // So does a user outside the tree, or an inner node passed off as a leaf
+        assert!(!WhitelistEntry::verify_claim(&root, &[ab], &Pubkey::new_unique(), 1));
+        assert!(!WhitelistEntry::verify_claim(&root, &[leaf_c], &alice, 0));
+
+        msg!("✓ Merkle proofs bind each claim to its user and tier");
+    }
+
+    #[test]
+    pub fn test_whitelist_list_offset() {
+        use {
+            crate::state::{HookConfig, PolicyMode},
+            anchor_lang::AccountSerialize,
+        };
+
+        let whitelist_list = Pubkey::new_unique();
+        let hook_config = HookConfig {
+            mint: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            pending_authority: Pubkey::new_unique(),
+            mode: PolicyMode::DenyList,
+            daily_limit: u64::MAX,
+            weekly_limit: u64::MAX,
+            whitelist_root: [7; 32],
+            paused: true,
+            rescue_authority: Pubkey::new_unique(),
+            whitelist_list,
+            transfer_log_enabled: true,
+            bump: 255,
+        };
+
+        let mut data = Vec::new();
+        hook_config.try_serialize(&mut data).unwrap();
+
+        // The ExtraAccountMetaList seeds read the list key straight out of the account data
+        let offset = HookConfig::WHITELIST_LIST_OFFSET;
+        assert_eq!(&data[offset..offset + 32], whitelist_list.as_ref());
+
+        msg!("✓ HookConfig::WHITELIST_LIST_OFFSET matches the serialized layout");
+    }
+
+    #[test]
+    pub fn test_transfer_log_ring_buffer() {
+        use crate::state::{TransferLog, TransferRecord, TRANSFER_LOG_CAPACITY};
+
+        let mut log = TransferLog {
+            mint: Pubkey::default(),
+            transfer_count: 0,
+            total_volume: 0,
+            records: [TransferRecord::default(); TRANSFER_LOG_CAPACITY],
+            bump: 0,
+        };
+        let record = |n: u64| TransferRecord {
+            amount: n,
+            slot: n,
+            ..TransferRecord::default()
+        };
+
+        log.append(record(1)).unwrap();
+        log.append(record(2)).unwrap();
+        assert_eq!(log.recent().map(|r| r.slot).collect::<Vec<_>>(), vec![1, 2]);
+
+        // Fill past capacity, the oldest records are overwritten
+        let total = TRANSFER_LOG_CAPACITY as u64 + 5;
+        for n in 3..=total {
+            log.append(record(n)).unwrap();
+        }
+        let slots = log.recent().map(|r| r.slot).collect::<Vec<_>>();
+        assert_eq!(slots.len(), TRANSFER_LOG_CAPACITY);
+        assert_eq!(slots.first(), Some(&6));
+        assert_eq!(slots.last(), Some(&total));
+
+        // Counter and volume cover every transfer, not just the retained ones
+        assert_eq!(log.transfer_count, total);
+        assert_eq!(log.total_volume, (total * (total + 1) / 2) as u128);
+
+        msg!("✓ Transfer log keeps the latest records and running totals");
+    }
+
+    #[test]
+    pub fn test_deny_list_mode() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+        ctx.execute_set_policy_mode(&mint, PolicyMode::DenyList);
+
+        // Without any entries everyone may transfer
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        ctx.execute_set_blocked(&mint, &bob.pubkey(), true);
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::DestinationBlocked)
+        );
+        assert_eq!(
+            ctx.try_transfer(&mint, &bob, &alice.pubkey(), 100),
+            hook_error(ErrorCode::SourceBlocked)
+        );
+
+        // Unblocking closes the entry and the transfer goes through again
+        ctx.execute_set_blocked(&mint, &bob.pubkey(), false);
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        msg!("✓ Deny-list mode passes when no BlockEntry exists");
+        msg!("✓ Blocked sources and destinations are rejected");
+    }
+
+    #[test]
+    pub fn test_batch_whitelist_operations() {
+        let mut ctx = HookTestContext::new();
+        let mint = ctx.setup_hooked_mint(&[], 0);
+        let users = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Someone pre-funds one entry address, which makes a plain create_account fail
+        let prefunded_entry = pda(&[b"whitelist", mint.as_ref(), users[1].as_ref()]);
+        let prefund_ix = system_instruction::transfer(&ctx.payer.pubkey(), &prefunded_entry, 1_000);
+        ctx.send(&[prefund_ix], &[]);
+
+        // Another user is already whitelisted and suspended at tier 1
+        ctx.execute_add_to_whitelist(&mint, &users[2], 0, 0);
+        ctx.execute_update_whitelist_entry(&mint, &users[2], 1, false, true, false);
+
+        // Adding it again one at a time fails rather than resetting its settings
+        let readd_ix = ctx.add_to_whitelist_ix(&mint, &users[2], 0, 0);
+        assert!(ctx.try_send(&[readd_ix], &[]).is_err());
+
+        // Entries out of order fail the seed check, as do mismatched lengths
+        let mut swapped_ix = ctx.batch_whitelist_ix(&mint, &users[..2], true);
+        swapped_ix.accounts.swap(4, 5);
+        assert_eq!(
+            ctx.try_send(&[swapped_ix], &[]).map(|_| ()),
+            hook_error(ErrorCode::InvalidWhitelistEntry)
+        );
+        let mut short_ix = ctx.batch_whitelist_ix(&mint, &users, true);
+        short_ix.accounts.pop();
+        assert_eq!(
+            ctx.try_send(&[short_ix], &[]).map(|_| ()),
+            hook_error(ErrorCode::BatchLengthMismatch)
+        );
+
+        let batch_add_ix = ctx.batch_whitelist_ix(&mint, &users, true);
+        ctx.send(&[batch_add_ix], &[]);
+
+        for user in &users[..2] {
+            let entry = ctx.get_whitelist_entry(&mint, user).unwrap();
+            assert!(entry.is_active && entry.can_send && entry.can_receive);
+            assert_eq!(entry.user, *user);
+        }
+        // The existing entry is left exactly as it was
+        let suspended = ctx.get_whitelist_entry(&mint, &users[2]).unwrap();
+        assert!(!suspended.is_active && suspended.can_send && !suspended.can_receive);
+        assert_eq!(suspended.tier, 1);
+
+        let batch_remove_ix = ctx.batch_whitelist_ix(&mint, &users, false);
+        ctx.send(&[batch_remove_ix], &[]);
+        for user in &users {
+            assert!(ctx.get_whitelist_entry(&mint, user).is_none());
+        }
+
+        msg!("✓ Batch add creates entries, including pre-funded addresses");
+        msg!("✓ Existing entries are skipped by batch add and rejected by add, not reset");
+        msg!("✓ Batch remove closes every entry");
+    }
+
+    #[test]
+    pub fn test_whitelist_validity_window() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+
+        let start = ctx.now();
+        ctx.execute_add_to_whitelist(&mint, &alice.pubkey(), start + 100, start + 1_000);
+        ctx.execute_add_to_whitelist(&mint, &bob.pubkey(), 0, 0);
+
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::WhitelistEntryNotYetValid)
+        );
+
+        ctx.warp_to(start + 100);
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        // Live entries cannot be pruned, even by the payer
+        assert_eq!(
+            ctx.try_prune_expired(&mint, &alice.pubkey()),
+            hook_error(ErrorCode::WhitelistEntryNotExpired)
+        );
+        assert_eq!(
+            ctx.try_prune_expired(&mint, &bob.pubkey()),
+            hook_error(ErrorCode::WhitelistEntryNotExpired)
+        );
+
+        ctx.warp_to(start + 1_000);
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::WhitelistEntryExpired)
+        );
+        // Bob's entry never expires, so receiving from him fails on Alice's side alone
+        assert_eq!(
+            ctx.try_transfer(&mint, &bob, &alice.pubkey(), 100),
+            hook_error(ErrorCode::WhitelistEntryExpired)
+        );
+
+        let payer_before = ctx.program.get_balance(&ctx.payer.pubkey()).unwrap();
+        ctx.try_prune_expired(&mint, &alice.pubkey()).unwrap();
+        assert!(ctx.get_whitelist_entry(&mint, &alice.pubkey()).is_none());
+        // The entry's rent goes back to its payer, net of the transaction fee
+        assert!(ctx.program.get_balance(&ctx.payer.pubkey()).unwrap() > payer_before);
+
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::SourceNotWhitelisted)
+        );
+
+        msg!("✓ Transfers outside the validity window are rejected");
+        msg!("✓ Only expired entries can be pruned");
+        msg!("✓ Pruning refunds the payer and removes the entry");
+    }
+
+    #[test]
+    pub fn test_update_extra_account_meta_list_realloc() {
+        let mut ctx = HookTestContext::new();
+        let mint = ctx.setup_hooked_mint(&[], 0);
+        let meta_list = pda(&[b"extra-account-metas", mint.as_ref()]);
+        let current = ctx.program.get_account(&meta_list).unwrap();
+        let metas = InitializeExtraAccountMetaList::extra_account_metas().unwrap();
+
+        // Only the mint's transfer-hook authority may rewrite the list
+        let stranger = Keypair::new();
+        ctx.program.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert_eq!(
+            ctx.try_update_extra_account_meta_list(&mint, &stranger),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::Unauthorized.into())
+            ))
+        );
+
+        let payer = ctx.payer.insecure_clone();
+
+        // A list from an older policy with fewer metas grows, topped up by the authority
+        ctx.set_extra_account_metas(&mint, &metas[..1]);
+        ctx.try_update_extra_account_meta_list(&mint, &payer).unwrap();
+        let grown = ctx.program.get_account(&meta_list).unwrap();
+        assert_eq!(grown.data, current.data);
+        assert_eq!(grown.lamports, current.lamports);
+
+        // A list with more metas shrinks and refunds the excess rent, which outweighs the fee
+        let unused_meta = ExtraAccountMeta::new_with_pubkey(&Pubkey::new_unique(), false, false);
+        let mut longer = metas.clone();
+        longer.push(unused_meta.unwrap());
+        ctx.set_extra_account_metas(&mint, &longer);
+        let payer_before = ctx.program.get_balance(&payer.pubkey()).unwrap();
+        ctx.try_update_extra_account_meta_list(&mint, &payer).unwrap();
+        let shrunk = ctx.program.get_account(&meta_list).unwrap();
+        assert_eq!(shrunk.data, current.data);
+        assert_eq!(shrunk.lamports, current.lamports);
+        assert!(ctx.program.get_balance(&payer.pubkey()).unwrap() > payer_before);
+
+        msg!("✓ Only the hook authority can update the meta list");
+        msg!("✓ Older, smaller lists grow to the current metas");
+        msg!("✓ Larger lists shrink and refund the excess rent");
+    }
+
+    #[test]
+    pub fn test_transfer_stats_follow_token_owner() {
+        use crate::state::TransferStats;
+
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let delegate = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+        ctx.execute_set_policy_mode(&mint, PolicyMode::DenyList);
+        ctx.execute_set_velocity_limits(&mint, 150, 0);
+        let alice_stats = ctx.execute_init_transfer_stats(&mint, &alice.pubkey());
+
+        let approve_ix = spl_token_2022::instruction::approve(
+            &TOKEN_2022_PROGRAM_ID,
+            &ata(&alice.pubkey(), &mint),
+            &delegate.pubkey(),
+            &alice.pubkey(),
+            &[],
+            1_000,
+        )
+        .unwrap();
+        ctx.send(&[approve_ix], &[&alice]);
+
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        // The delegate spends from Alice's daily allowance, not a fresh one of its own
+        assert_eq!(
+            ctx.try_transfer_from(&mint, &alice.pubkey(), &delegate, &bob.pubkey(), 100),
+            hook_error(ErrorCode::DailyLimitExceeded)
+        );
+        ctx.try_transfer_from(&mint, &alice.pubkey(), &delegate, &bob.pubkey(), 50)
+            .unwrap();
+
+        let account = ctx.program.get_account(&alice_stats).unwrap();
+        let stats = TransferStats::try_deserialize(&mut account.data.as_ref()).unwrap();
+        assert_eq!(stats.daily_volumes.iter().sum::<u64>(), 150);
+
+        msg!("✓ Delegated transfers count against the token account owner");
+    }
+
+    #[test]
+    pub fn test_hook_config_migration() {
+        use crate::state::HookConfig;
+
+        // Layout before the policy fields: mint, authority, pending_authority, bump
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut data = HookConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(Pubkey::default().as_ref());
+        data.push(253);
+        let old_len = data.len();
+
+        // Realloc zero-fills the new bytes
+        data.resize(HookConfig::LEN, 0);
+        HookConfig::migrate_layout(&mut data, old_len);
+
+        let hook_config = HookConfig::try_deserialize(&mut data.as_ref()).unwrap();
+        assert_eq!(hook_config.mint, mint);
+        assert_eq!(hook_config.authority, authority);
+        assert_eq!(hook_config.bump, 253);
+
+        // Every added field reads as its default
+        assert!(hook_config.mode == PolicyMode::AllowList);
+        assert_eq!((hook_config.daily_limit, hook_config.weekly_limit), (0, 0));
+        assert_eq!(hook_config.whitelist_root, [0; 32]);
+        assert!(!hook_config.paused);
+        assert_eq!(hook_config.rescue_authority, Pubkey::default());
+        assert_eq!(hook_config.whitelist_list, Pubkey::default());
+        assert!(!hook_config.transfer_log_enabled);
+
+        msg!("✓ Migration moves the bump to the end and zeroes the new fields");
+    }
+
+    #[test]
+    pub fn test_tier_limits() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let carol = Keypair::new();
+        let holders = [&alice.pubkey(), &bob.pubkey(), &carol.pubkey()];
+        let mint = ctx.setup_hooked_mint(&holders, 1_000);
+
+        for (tier, holder) in holders.into_iter().enumerate() {
+            ctx.execute_add_to_whitelist(&mint, holder, 0, 0);
+            ctx.execute_update_whitelist_entry(&mint, holder, tier as u8, true, true, true);
+        }
+
+        // Tier 0 may only send to tiers 0 and 1, 100 at a time and 150 a week
+        ctx.execute_set_tier_limits(
+            &mint,
+            0,
+            TierLimits {
+                max_transfer_amount: 100,
+                weekly_cumulative_limit: 150,
+                allowed_counterparty_tiers: 0b011,
+            },
+        );
+
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &carol.pubkey(), 10),
+            hook_error(ErrorCode::CounterpartyTierNotAllowed)
+        );
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 101),
+            hook_error(ErrorCode::ExceedsMaxTransferAmount)
+        );
+
+        // The weekly limit needs Alice's stats, and the error says it's the tier's
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::TierTransferStatsNotInitialized)
+        );
+        ctx.execute_init_transfer_stats(&mint, &alice.pubkey());
+
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::WeeklyLimitExceeded)
+        );
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 50).unwrap();
+
+        // Tiers that were never set stay unrestricted
+        ctx.try_transfer(&mint, &bob, &carol.pubkey(), 500).unwrap();
+
+        msg!("✓ Counterparty tiers and max transfer amounts are enforced");
+        msg!("✓ Weekly cumulative limits need and update the holder's stats");
+    }
+
+    #[test]
+    pub fn test_direction_flags() {
+        let mut ctx = HookTestContext::new();
+        let treasury = Keypair::new();
+        let bob = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&treasury.pubkey(), &bob.pubkey()], 1_000);
+        ctx.execute_add_to_whitelist(&mint, &treasury.pubkey(), 0, 0);
+        ctx.execute_add_to_whitelist(&mint, &bob.pubkey(), 0, 0);
+
+        // A receive-only treasury accepts tokens but cannot send them on
+        ctx.execute_update_whitelist_entry(&mint, &treasury.pubkey(), 0, true, false, true);
+        assert_eq!(
+            ctx.try_transfer(&mint, &treasury, &bob.pubkey(), 100),
+            hook_error(ErrorCode::SourceCannotSend)
+        );
+        ctx.try_transfer(&mint, &bob, &treasury.pubkey(), 100).unwrap();
+
+        // A send-only account may return tokens but not take new ones
+        ctx.execute_update_whitelist_entry(&mint, &treasury.pubkey(), 0, true, true, true);
+        ctx.execute_update_whitelist_entry(&mint, &bob.pubkey(), 0, true, true, false);
+        assert_eq!(
+            ctx.try_transfer(&mint, &treasury, &bob.pubkey(), 100),
+            hook_error(ErrorCode::DestinationCannotReceive)
+        );
+        ctx.try_transfer(&mint, &bob, &treasury.pubkey(), 100).unwrap();
+
+        // Suspension overrides both flags
+        ctx.execute_update_whitelist_entry(&mint, &treasury.pubkey(), 0, false, true, true);
+        assert_eq!(
+            ctx.try_transfer(&mint, &bob, &treasury.pubkey(), 100),
+            hook_error(ErrorCode::WhitelistEntryInactive)
+        );
+
+        msg!("✓ can_send is checked on the source, can_receive on the destination");
+        msg!("✓ Suspended entries can neither send nor receive");
+    }
+
+    #[test]
+    pub fn test_program_whitelist_requires_pda() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let escrow_program = Pubkey::new_unique();
+        let (escrow, _) = Pubkey::find_program_address(&[b"offer", &[1]], &escrow_program);
+        // A keypair that assigned its own account to the escrow program
+        let imposter = Keypair::new();
+
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey()], 1_000);
+        ctx.execute_add_to_whitelist(&mint, &alice.pubkey(), 0, 0);
+        ctx.execute_add_program_to_whitelist(&mint, &escrow_program);
+
+        ctx.set_program_account(&escrow, &escrow_program);
+        ctx.set_program_account(&imposter.pubkey(), &escrow_program);
+        ctx.execute_create_ata(&mint, &escrow);
+        ctx.execute_create_ata(&mint, &imposter.pubkey());
+
+        ctx.try_transfer(&mint, &alice, &escrow, 100).unwrap();
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &imposter.pubkey(), 100),
+            hook_error(ErrorCode::DestinationNotWhitelisted)
+        );
+
+        // The imposter signs for its own tokens but gets no fallback as a source either
+        ctx.execute_mint_to(&mint, &imposter.pubkey(), 100);
+        assert_eq!(
+            ctx.try_transfer(&mint, &imposter, &alice.pubkey(), 100),
+            hook_error(ErrorCode::SourceNotWhitelisted)
+        );
+
+        msg!("✓ PDAs of an approved program can receive without an entry");
+        msg!("✓ On-curve owners assigned to the program are rejected");
+    }
+
+    #[test]
+    pub fn test_removed_user_cannot_reclaim() {
+        use crate::state::WhitelistEntry;
+
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+        ctx.program.airdrop(&alice.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        ctx.execute_add_to_whitelist(&mint, &bob.pubkey(), 0, 0);
+
+        // A one-leaf tree: the root is Alice's leaf and the proof is empty
+        ctx.execute_set_whitelist_root(&mint, WhitelistEntry::claim_leaf(&alice.pubkey(), 0));
+        ctx.try_claim_whitelist(&mint, &alice, 0, vec![]).unwrap();
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        // Removal closes the entry and refunds its rent
+        let admin_before = ctx.program.get_balance(&ctx.payer.pubkey()).unwrap();
+        ctx.execute_remove_from_whitelist(&mint, &alice.pubkey());
+        assert!(ctx.get_whitelist_entry(&mint, &alice.pubkey()).is_none());
+        assert!(ctx.program.get_balance(&ctx.payer.pubkey()).unwrap() > admin_before);
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::SourceNotWhitelisted)
+        );
+
+        // Alice is still in the old tree, but removal cleared its root
+        assert_eq!(
+            ctx.try_claim_whitelist(&mint, &alice, 0, vec![]),
+            hook_error(ErrorCode::WhitelistRootNotSet)
+        );
+
+        // The next tree leaves her out
+        let carol = Pubkey::new_unique();
+        ctx.execute_set_whitelist_root(&mint, WhitelistEntry::claim_leaf(&carol, 0));
+        assert_eq!(
+            ctx.try_claim_whitelist(&mint, &alice, 0, vec![]),
+            hook_error(ErrorCode::InvalidMerkleProof)
+        );
+
+        // Only the authority can bring her back
+        ctx.execute_add_to_whitelist(&mint, &alice.pubkey(), 0, 0);
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        msg!("✓ Removal closes the entry and clears the whitelist root");
+        msg!("✓ A removed user cannot re-claim with a valid proof");
+    }
+
+    #[test]
+    pub fn test_pause_and_exemptions() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        // Neither exempt authority is whitelisted or has transfer stats
+        let rescue = Keypair::new();
+        let permanent_delegate = ctx.payer.insecure_clone();
+        let holders = [&alice.pubkey(), &bob.pubkey(), &rescue.pubkey()];
+        let mint = ctx.setup_hooked_mint(&holders, 1_000);
+        ctx.execute_add_to_whitelist(&mint, &alice.pubkey(), 0, 0);
+        ctx.execute_add_to_whitelist(&mint, &bob.pubkey(), 0, 0);
+        ctx.execute_set_rescue_authority(&mint, &rescue.pubkey());
+        ctx.execute_set_velocity_limits(&mint, 50, 0);
+
+        ctx.execute_set_paused(&mint, true);
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 10),
+            hook_error(ErrorCode::TransfersPaused)
+        );
+
+        // Exempt transfers skip the allow-list and the velocity limits
+        ctx.try_transfer(&mint, &rescue, &bob.pubkey(), 100).unwrap();
+        ctx.try_transfer_from(&mint, &alice.pubkey(), &permanent_delegate, &rescue.pubkey(), 100)
+            .unwrap();
+
+        ctx.execute_set_paused(&mint, false);
+        ctx.execute_set_velocity_limits(&mint, 0, 0);
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 10).unwrap();
+        ctx.try_transfer_from(&mint, &alice.pubkey(), &permanent_delegate, &bob.pubkey(), 10)
+            .unwrap();
+        // Once unpaused the rescue authority is subject to the policy like everyone else
+        assert_eq!(
+            ctx.try_transfer(&mint, &rescue, &bob.pubkey(), 10),
+            hook_error(ErrorCode::SourceNotWhitelisted)
+        );
+
+        msg!("✓ Paused mints reject ordinary transfers");
+        msg!("✓ The rescue authority and permanent delegate bypass the policy while paused");
+        msg!("✓ Unpausing restores the normal policy");
+        msg!("✓ The permanent delegate passes the authority check");
+    }
+
+    #[test]
+    pub fn test_shared_whitelist_list() {
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let kyc_provider = Keypair::new();
+        ctx.program
+            .airdrop(&kyc_provider.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+
+        let expiry = ctx.now() + 100;
+        let list = ctx.execute_create_whitelist_list(&kyc_provider, 0);
+        ctx.execute_add_to_whitelist_list(&list, &kyc_provider, &alice.pubkey(), 0);
+        ctx.execute_add_to_whitelist_list(&list, &kyc_provider, &bob.pubkey(), expiry);
+
+        // The mint authority cannot adopt someone else's list on its own
+        assert_eq!(
+            ctx.try_set_whitelist_list(&mint, &list, None),
+            hook_error(ErrorCode::Unauthorized)
+        );
+        ctx.try_set_whitelist_list(&mint, &list, Some(&kyc_provider))
+            .unwrap();
+
+        // Neither holder has an entry for this mint, the list covers both
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+
+        assert_eq!(
+            ctx.try_prune_expired_list_entry(&list, &kyc_provider.pubkey(), &bob.pubkey()),
+            hook_error(ErrorCode::WhitelistEntryNotExpired)
+        );
+
+        ctx.warp_to(expiry);
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::WhitelistEntryExpired)
+        );
+
+        ctx.try_prune_expired_list_entry(&list, &kyc_provider.pubkey(), &bob.pubkey())
+            .unwrap();
+        assert_eq!(
+            ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100),
+            hook_error(ErrorCode::DestinationNotWhitelisted)
+        );
+
+        msg!("✓ Attaching a shared list needs the list authority's signature");
+        msg!("✓ Transfers resolve entries through the shared list");
+        msg!("✓ Expired list entries can be pruned");
+    }
+
+    #[test]
+    pub fn test_transfer_log_records_transfers() {
+        use crate::state::{HookConfig, TransferLog};
+
+        let mut ctx = HookTestContext::new();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let mint = ctx.setup_hooked_mint(&[&alice.pubkey(), &bob.pubkey()], 1_000);
+        ctx.execute_set_policy_mode(&mint, PolicyMode::DenyList);
+
+        // Logging is off until the log is created
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 10).unwrap();
+        let transfer_log = ctx.execute_init_transfer_log(&mint);
+
+        let account = ctx
+            .program
+            .get_account(&pda(&[b"hook_config", mint.as_ref()]))
+            .unwrap();
+        let hook_config = HookConfig::try_deserialize(&mut account.data.as_ref()).unwrap();
+        assert!(hook_config.transfer_log_enabled);
+
+        ctx.try_transfer(&mint, &alice, &bob.pubkey(), 100).unwrap();
+        ctx.try_transfer(&mint, &bob, &alice.pubkey(), 40).unwrap();
+
+        // A rescue by the permanent delegate while paused is logged too
+        let permanent_delegate = ctx.payer.insecure_clone();
+        ctx.execute_set_paused(&mint, true);
+        ctx.try_transfer_from(&mint, &alice.pubkey(), &permanent_delegate, &bob.pubkey(), 25)
+            .unwrap();
+
+        let account = ctx.program.get_account(&transfer_log).unwrap();
+        let log = TransferLog::try_deserialize(&mut account.data.as_ref()).unwrap();
+        assert_eq!(log.transfer_count, 3);
+        assert_eq!(log.total_volume, 165);
+
+        let records = log.recent().collect::<Vec<_>>();
+        assert_eq!(records[0].source, ata(&alice.pubkey(), &mint));
+        assert_eq!(records[0].destination, ata(&bob.pubkey(), &mint));
+        assert_eq!(records[0].amount, 100);
+        assert_eq!(records[1].source, ata(&bob.pubkey(), &mint));
+        assert_eq!(records[1].amount, 40);
+        assert_eq!(records[2].source, ata(&alice.pubkey(), &mint));
+        assert_eq!(records[2].amount, 25);
+
+        msg!("✓ Creating the log enables logging on the hook config");
+        msg!("✓ Every transfer afterwards is appended to the log, exempt ones included");
+    }
+
+    #[test]
+    pub fn test_whitelist_entry_migration() {
+        use crate::state::WhitelistEntry;
+
+        // Legacy layout: user, is_active, max_transfer_amount, bump
+        let legacy = |user: &Pubkey, max_transfer_amount: u64| {
+            let mut data = WhitelistEntry::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(user.as_ref());
+            data.push(1);
+            data.extend_from_slice(&max_transfer_amount.to_le_bytes());
+            data.push(252);
+            assert_eq!(data.len(), WhitelistEntry::LEGACY_LEN);
+
+            // Realloc zero-fills the new bytes
+            data.resize(WhitelistEntry::LEN, 0);
+            data
+        };
+        let user = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let mut data = legacy(&user, 0);
+        WhitelistEntry::migrate_layout(&mut data, authority).unwrap();
+        let entry = WhitelistEntry::try_deserialize(&mut data.as_ref()).unwrap();
+        assert_eq!(entry.user, user);
+        assert_eq!(entry.bump, 252);
+        assert_eq!(entry.payer, authority);
+        assert!(entry.is_active && entry.can_send && entry.can_receive);
+        assert_eq!((entry.tier, entry.valid_from, entry.valid_until), (0, 0, 0));
+
+        // A per-entry cap has no equivalent any more, so the entry waits for a tier
+        let mut data = legacy(&user, 500);
+        WhitelistEntry::migrate_layout(&mut data, authority).unwrap();
+        let entry = WhitelistEntry::try_deserialize(&mut data.as_ref()).unwrap();
+        assert!(!entry.is_active);
+
+        msg!("✓ Legacy whitelist entries migrate to the current layout");
+        msg!("✓ Capped legacy entries come back suspended");
+    }
+}
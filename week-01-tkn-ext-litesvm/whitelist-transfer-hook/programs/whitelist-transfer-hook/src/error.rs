@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Signer is not the hook authority for this mint")]
+    Unauthorized,
+
+    #[msg("Mint has neither a transfer-hook authority nor a mint authority")]
+    NoHookAuthority,
+
+    #[msg("Signer is not the pending hook authority")]
+    NotPendingAuthority,
+
+    #[msg("Transfer hook was not invoked during a token transfer")]
+    NotTransferring,
+}
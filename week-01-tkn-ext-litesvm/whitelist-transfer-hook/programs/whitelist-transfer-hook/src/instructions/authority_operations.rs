@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::ErrorCode, state::HookConfig};
+
+#[derive(Accounts)]
+pub struct NominateAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        has_one = pending_authority @ ErrorCode::NotPendingAuthority,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+}
+
+impl<'info> NominateAuthority<'info> {
+    /// Takes effect only once the new authority accepts, so a typo can't lock the whitelist.
+    /// Nominating `Pubkey::default()` cancels a pending rotation.
+    pub fn nominate_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        self.hook_config.pending_authority = new_authority;
+        Ok(())
+    }
+}
+
+impl<'info> AcceptAuthority<'info> {
+    pub fn accept_authority(&mut self) -> Result<()> {
+        self.hook_config.authority = self.pending_authority.key();
+        self.hook_config.pending_authority = Pubkey::default();
+        Ok(())
+    }
+}
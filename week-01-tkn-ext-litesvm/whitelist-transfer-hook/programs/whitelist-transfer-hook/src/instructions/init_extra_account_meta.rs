@@ -4,6 +4,8 @@ use spl_tlv_account_resolution::{
     account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
 };
 
+use crate::state::HookConfig;
+
 #[derive(Accounts)]
 pub struct InitializeExtraAccountMetaList<'info> {
     #[account(mut)]
@@ -21,10 +23,31 @@ pub struct InitializeExtraAccountMetaList<'info> {
     )]
     pub extra_account_meta_list: AccountInfo<'info>,
     pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HookConfig::INIT_SPACE,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitializeExtraAccountMetaList<'info> {
+    /// The whitelist authority is taken from the mint, not from whoever pays for init
+    pub fn init_hook_config(&mut self, bumps: &InitializeExtraAccountMetaListBumps) -> Result<()> {
+        self.hook_config.set_inner(HookConfig {
+            mint: self.mint.key(),
+            authority: HookConfig::default_authority(&self.mint)?,
+            pending_authority: Pubkey::default(),
+            bump: bumps.hook_config,
+        });
+        Ok(())
+    }
+
     pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
         Ok(vec![ExtraAccountMeta::new_with_seeds(
             &[
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
-// use spl_tlv_account_resolution::state::ExtraAccountMetaList;
-// use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
-// use crate::instructions::InitializeExtraAccountMetaList;
+use crate::{
+    instructions::InitializeExtraAccountMetaList,
+    state::{HookConfig, WhitelistEntry},
+};
 
 #[derive(Accounts)]
 pub struct TokenFactory<'info> {
@@ -21,28 +24,66 @@ pub struct TokenFactory<'info> {
     pub mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: ExtraAccountMetaList Account, will be checked by the transfer hook
-    // #[account(
-    //     init,
-    //     payer = user,
-    //     space = ExtraAccountMetaList::size_of(
-    //         InitializeExtraAccountMetaList::extra_account_metas()?.len()
-    //     )?,
-    //     seeds = [b"extra-account-metas", mint.key().as_ref()],
-    //     bump
-    // )]
-    // pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = user,
+        space = ExtraAccountMetaList::size_of(
+            InitializeExtraAccountMetaList::extra_account_metas()?.len()
+        )?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + HookConfig::INIT_SPACE,
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
+    /// Pass this to whitelist the creator in the same instruction, or omit it
+    #[account(
+        init,
+        payer = user,
+        space = 8 + WhitelistEntry::INIT_SPACE,
+        seeds = [b"whitelist", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub creator_whitelist_entry: Option<Account<'info, WhitelistEntry>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> TokenFactory<'info> {
-    pub fn init_mint(&mut self, _bumps: &TokenFactoryBumps) -> Result<()> {
-        // let extra_account_metas = InitializeExtraAccountMetaList::extra_account_metas()?;
+    /// Creates the hooked mint together with its ExtraAccountMetaList,
+    /// so the mint can never exist without the accounts its transfers need
+    pub fn init_mint(&mut self, bumps: &TokenFactoryBumps) -> Result<()> {
+        let extra_account_metas = InitializeExtraAccountMetaList::extra_account_metas()?;
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut self.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+
+        // Same rule as the standalone initialize: the mint's transfer-hook authority
+        self.hook_config.set_inner(HookConfig {
+            mint: self.mint.key(),
+            authority: HookConfig::default_authority(&self.mint)?,
+            pending_authority: Pubkey::default(),
+            bump: bumps.hook_config,
+        });
 
-        // ExtraAccountMetaList::init::<ExecuteInstruction>(
-        //     &mut self.extra_account_meta_list.try_borrow_mut_data()?,
-        //     &extra_account_metas,
-        // )?;
+        // Anchor sets the bump exactly when the optional entry is passed
+        if let (Some(creator_whitelist_entry), Some(bump)) = (
+            &mut self.creator_whitelist_entry,
+            bumps.creator_whitelist_entry,
+        ) {
+            creator_whitelist_entry.set_inner(WhitelistEntry { bump });
+        }
 
         Ok(())
     }
@@ -1,9 +1,13 @@
+pub mod authority_operations;
 pub mod init_extra_account_meta;
 pub mod mint_token;
 pub mod transfer_hook;
+pub mod update_extra_account_meta;
 pub mod whitelist_operations;
 
+pub use authority_operations::*;
 pub use init_extra_account_meta::*;
 pub use mint_token::*;
 pub use transfer_hook::*;
+pub use update_extra_account_meta::*;
 pub use whitelist_operations::*;
@@ -1,12 +1,12 @@
-use std::cell::RefMut;
+use std::cell::Ref;
 
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token_2022::spl_token_2022::{
         extension::{
             transfer_hook::TransferHookAccount, 
-            BaseStateWithExtensionsMut, 
-            PodStateWithExtensionsMut
+            BaseStateWithExtensions, 
+            PodStateWithExtensions
         }, 
         pod::PodAccount
     }, 
@@ -16,7 +16,7 @@ use anchor_spl::{
     }
 };
 
-use crate::state::WhitelistEntry;
+use crate::{error::ErrorCode, state::WhitelistEntry};
 
 #[derive(Accounts)]
 pub struct TransferHook<'info> {
@@ -58,27 +58,28 @@ impl<'info> TransferHook<'info> {
     }
 
     /// Checks if the transfer hook is being executed during a transfer operation.
-    fn check_is_transferring(&mut self) -> Result<()> {
+    fn check_is_transferring(&self) -> Result<()> {
         // Ensure that the source token account has the transfer hook extension enabled
 
         // Get the account info of the source token account
         let source_token_info = self.source_token.to_account_info();
-        // Borrow the account data mutably
-        let mut account_data_ref: RefMut<&mut [u8]> = source_token_info.try_borrow_mut_data()?;
+        // Borrow the account data, reading the flag needs no mutable access
+        let account_data_ref: Ref<&mut [u8]> = source_token_info.try_borrow_data()?;
 
-        // Unpack the account data as a PodStateWithExtensionsMut
+        // Unpack the account data as a PodStateWithExtensions
         // This will allow us to access the extensions of the token account
-        // We use PodStateWithExtensionsMut because TokenAccount is a POD (Plain Old Data) type
-        let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(*account_data_ref)?;
+        // We use PodStateWithExtensions because TokenAccount is a POD (Plain Old Data) type
+        let account = PodStateWithExtensions::<PodAccount>::unpack(*account_data_ref)?;
         // Get the TransferHookAccount extension
         // Search for the TransferHookAccount extension in the token account
         // The returning struct has a `transferring` field that indicates if the account is in the middle of a transfer operation
-        let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
+        let account_extension = account.get_extension::<TransferHookAccount>()?;
     
         // Check if the account is in the middle of a transfer operation
-        if !bool::from(account_extension.transferring) {
-            panic!("TransferHook: Not transferring");
-        }
+        require!(
+            bool::from(account_extension.transferring),
+            ErrorCode::NotTransferring
+        );
     
         Ok(())
     }
@@ -0,0 +1,97 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::transfer_hook::TransferHook,
+    token_interface::{get_mint_extension_data, Mint},
+};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::{error::ErrorCode, instructions::InitializeExtraAccountMetaList};
+
+/// The interface's `UpdateExtraAccountMetaList`. As with initialization, the
+/// metas come from this program and any passed in the instruction data are ignored
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    /// CHECK: ExtraAccountMetaList Account, must use these seeds
+    #[account(
+        mut,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The mint's transfer-hook authority. Pays for growing the list and
+    /// receives the rent back when it shrinks
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Passed after the interface's accounts, funds the rent top-up when the list grows
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateExtraAccountMetaList<'info> {
+    /// Rewrites the list with the metas this program currently resolves,
+    /// so mints created under an older policy pick up the new accounts
+    pub fn update_extra_account_meta_list(&mut self) -> Result<()> {
+        let hook_authority = get_mint_extension_data::<TransferHook>(&self.mint.to_account_info())
+            .ok()
+            .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.authority));
+        require!(
+            hook_authority == Some(self.authority.key()),
+            ErrorCode::Unauthorized
+        );
+
+        let extra_account_metas = InitializeExtraAccountMetaList::extra_account_metas()?;
+
+        let meta_list = self.extra_account_meta_list.to_account_info();
+        let rent = Rent::get()?;
+        let old_len = meta_list.data_len();
+        let new_len = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+
+        // Grow before writing so the TLV entry has room
+        if new_len > old_len {
+            let extra_lamports = rent
+                .minimum_balance(new_len)
+                .saturating_sub(meta_list.lamports());
+
+            if extra_lamports > 0 {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.authority.to_account_info(),
+                            to: meta_list.clone(),
+                        },
+                    ),
+                    extra_lamports,
+                )?;
+            }
+
+            meta_list.realloc(new_len, false)?;
+        }
+
+        ExtraAccountMetaList::update::<ExecuteInstruction>(
+            &mut meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+
+        // Shrink after writing and refund the rent that is no longer needed
+        if new_len < old_len {
+            meta_list.realloc(new_len, false)?;
+
+            let excess_lamports = meta_list
+                .lamports()
+                .saturating_sub(rent.minimum_balance(new_len));
+
+            **meta_list.try_borrow_mut_lamports()? -= excess_lamports;
+            **self.authority.try_borrow_mut_lamports()? += excess_lamports;
+        }
+
+        Ok(())
+    }
+}
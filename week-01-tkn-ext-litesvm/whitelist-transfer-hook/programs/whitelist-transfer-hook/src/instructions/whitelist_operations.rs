@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
-use crate::state::whitelist::WhitelistEntry;
+use crate::{
+    error::ErrorCode,
+    state::{HookConfig, WhitelistEntry},
+};
 
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
@@ -11,6 +14,13 @@ pub struct AddToWhitelist<'info> {
 
     pub mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
     #[account(
         init_if_needed,
         payer = admin,
@@ -31,6 +41,13 @@ pub struct RemoveFromWhitelist<'info> {
 
     pub mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        seeds = [b"hook_config", mint.key().as_ref()],
+        bump = hook_config.bump,
+        constraint = hook_config.authority == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub hook_config: Account<'info, HookConfig>,
+
     #[account(
         mut,
         close = admin,
@@ -1,6 +1,9 @@
 #![allow(unexpected_cfgs, deprecated)]
+pub mod error;
 mod instructions;
 mod state;
+#[allow(clippy::module_inception)]
+pub mod tests;
 
 use anchor_lang::prelude::*;
 use instructions::*;
@@ -9,6 +12,7 @@ use spl_discriminator::SplDiscriminate;
 use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_transfer_hook_interface::instruction::{
     ExecuteInstruction, InitializeExtraAccountMetaListInstruction,
+    UpdateExtraAccountMetaListInstruction,
 };
 
 declare_id!("781HqcZiSsq5Psgqxo3Lk1QTDGYdJJfsNksjLc6Rq4mw");
@@ -25,6 +29,14 @@ pub mod whitelist_transfer_hook {
         ctx.accounts.remove_from_whitelist(user)
     }
 
+    pub fn nominate_authority(ctx: Context<NominateAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.nominate_authority(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()
+    }
+
     #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
     pub fn initialize_transfer_hook(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
         msg!("Initializing Transfer Hook...");
@@ -41,7 +53,12 @@ pub mod whitelist_transfer_hook {
             &extra_account_metas,
         )?;
 
-        Ok(())
+        ctx.accounts.init_hook_config(&ctx.bumps)
+    }
+
+    #[instruction(discriminator = UpdateExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn update_transfer_hook(ctx: Context<UpdateExtraAccountMetaList>) -> Result<()> {
+        ctx.accounts.update_extra_account_meta_list()
     }
 
     #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::transfer_hook::TransferHook,
+    token_interface::{get_mint_extension_data, Mint},
+};
+
+use crate::error::ErrorCode;
+
+#[account]
+#[derive(InitSpace)]
+pub struct HookConfig {
+    pub mint: Pubkey,
+    /// Only this key may edit the mint's whitelist
+    pub authority: Pubkey,
+    /// Nominated by the current authority, `Pubkey::default()` when none
+    pub pending_authority: Pubkey,
+    pub bump: u8,
+}
+
+impl HookConfig {
+    /// The mint's transfer-hook authority, falling back to its mint authority
+    pub fn default_authority(mint: &InterfaceAccount<Mint>) -> Result<Pubkey> {
+        let hook_authority = get_mint_extension_data::<TransferHook>(&mint.to_account_info())
+            .ok()
+            .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.authority));
+
+        hook_authority
+            .or(mint.mint_authority.into())
+            .ok_or(ErrorCode::NoHookAuthority.into())
+    }
+}
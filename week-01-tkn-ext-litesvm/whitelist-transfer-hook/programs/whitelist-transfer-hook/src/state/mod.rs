@@ -1,3 +1,5 @@
+pub mod hook_config;
 pub mod whitelist;
 
+pub use hook_config::*;
 pub use whitelist::*;
@@ -0,0 +1,348 @@
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{error::ErrorCode, instructions::InitializeExtraAccountMetaList},
+        anchor_lang::{
+            prelude::msg, solana_program::system_instruction, AccountDeserialize, InstructionData,
+            ToAccountMetas,
+        },
+        anchor_spl::{
+            associated_token::spl_associated_token_account,
+            token_2022::spl_token_2022::{
+                self,
+                extension::{
+                    transfer_hook::{self, TransferHook},
+                    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+                },
+                state::Mint as SplMint,
+            },
+        },
+        litesvm::{types::TransactionMetadata, LiteSVM},
+        solana_instruction::{error::InstructionError, Instruction},
+        solana_keypair::Keypair,
+        solana_message::Message,
+        solana_native_token::LAMPORTS_PER_SOL,
+        solana_pubkey::Pubkey,
+        solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+        solana_signer::Signer,
+        solana_transaction::Transaction,
+        solana_transaction_error::TransactionError,
+        spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+        spl_transfer_hook_interface::instruction::ExecuteInstruction,
+    };
+
+    static PROGRAM_ID: Pubkey = crate::ID;
+    static TOKEN_2022_PROGRAM_ID: Pubkey =
+        solana_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+    fn setup() -> (LiteSVM, Keypair) {
+        let mut program = LiteSVM::new();
+        let payer = Keypair::new();
+
+        program
+            .airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("Failed to airdrop SOL to payer");
+
+        program
+            .add_program_from_file(PROGRAM_ID, "../../target/deploy/whitelist_transfer_hook.so")
+            .expect("Failed to load transfer hook program");
+
+        (program, payer)
+    }
+
+    fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+    }
+
+    struct HookTestContext {
+        program: LiteSVM,
+        payer: Keypair,
+    }
+
+    impl HookTestContext {
+        fn new() -> Self {
+            let (program, payer) = setup();
+            Self { program, payer }
+        }
+
+        fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) {
+            let tx = self.try_send(instructions, signers).unwrap();
+
+            msg!("CUs Consumed: {}", tx.compute_units_consumed);
+            msg!("Tx Signature: {}\n", tx.signature);
+        }
+
+        fn try_send(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<TransactionMetadata, TransactionError> {
+            // Repeating a transaction would reuse its signature, so each one gets a new blockhash
+            self.program.expire_blockhash();
+
+            let message = Message::new(instructions, Some(&self.payer.pubkey()));
+            let mut all_signers = vec![&self.payer];
+            all_signers.extend_from_slice(signers);
+            let transaction =
+                Transaction::new(&all_signers, message, self.program.latest_blockhash());
+            self.program
+                .send_transaction(transaction)
+                .map_err(|failure| failure.err)
+        }
+
+        /// Creates a 9-decimal mint whose transfer hook points at this program
+        fn execute_create_hooked_mint(&mut self) -> Pubkey {
+            let mint = Keypair::new();
+            let space =
+                ExtensionType::try_calculate_account_len::<SplMint>(&[ExtensionType::TransferHook])
+                    .unwrap();
+
+            let instructions = [
+                system_instruction::create_account(
+                    &self.payer.pubkey(),
+                    &mint.pubkey(),
+                    self.program.minimum_balance_for_rent_exemption(space),
+                    space as u64,
+                    &TOKEN_2022_PROGRAM_ID,
+                ),
+                transfer_hook::instruction::initialize(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    Some(self.payer.pubkey()),
+                    Some(PROGRAM_ID),
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_mint2(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &self.payer.pubkey(),
+                    None,
+                    9,
+                )
+                .unwrap(),
+            ];
+
+            self.send(&instructions, &[&mint]);
+            msg!("Hooked mint created: {}", mint.pubkey());
+
+            mint.pubkey()
+        }
+
+        fn execute_initialize_transfer_hook(&mut self, mint: &Pubkey) {
+            let initialize_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::InitializeExtraAccountMetaList {
+                    payer: self.payer.pubkey(),
+                    extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::InitializeTransferHook {}.data(),
+            };
+
+            self.send(&[initialize_ix], &[]);
+            msg!("ExtraAccountMetaList initialized");
+        }
+
+        fn execute_add_to_whitelist(&mut self, mint: &Pubkey, user: &Pubkey) -> Pubkey {
+            let whitelist_entry = pda(&[b"whitelist", mint.as_ref(), user.as_ref()]);
+
+            let add_to_whitelist_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::AddToWhitelist {
+                    admin: self.payer.pubkey(),
+                    mint: *mint,
+                    hook_config: pda(&[b"hook_config", mint.as_ref()]),
+                    whitelist_entry,
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::AddToWhitelist { user: *user }.data(),
+            };
+
+            self.send(&[add_to_whitelist_ix], &[]);
+            msg!("Whitelisted: {}", user);
+
+            whitelist_entry
+        }
+
+        fn try_update_extra_account_meta_list(
+            &mut self,
+            mint: &Pubkey,
+            authority: &Keypair,
+        ) -> Result<(), TransactionError> {
+            let update_ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: crate::accounts::UpdateExtraAccountMetaList {
+                    extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                    mint: *mint,
+                    authority: authority.pubkey(),
+                    system_program: SYSTEM_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::UpdateTransferHook {}.data(),
+            };
+
+            self.try_send(&[update_ix], &[authority]).map(|_| ())
+        }
+
+        /// Overwrites the mint's meta list with `metas`, funded at the rent minimum
+        fn set_extra_account_metas(&mut self, mint: &Pubkey, metas: &[ExtraAccountMeta]) {
+            let meta_list = pda(&[b"extra-account-metas", mint.as_ref()]);
+            let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+            ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, metas).unwrap();
+
+            let mut account = self.program.get_account(&meta_list).unwrap();
+            account.lamports = self.program.minimum_balance_for_rent_exemption(data.len());
+            account.data = data;
+            self.program.set_account(meta_list, account).unwrap();
+        }
+
+        fn execute_create_ata(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+            let create_ata_ix =
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &self.payer.pubkey(),
+                    owner,
+                    mint,
+                    &TOKEN_2022_PROGRAM_ID,
+                );
+
+            self.send(&[create_ata_ix], &[]);
+
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                owner,
+                mint,
+                &TOKEN_2022_PROGRAM_ID,
+            )
+        }
+    }
+
+    #[test]
+    pub fn test_transfer_hook_outside_transfer_fails() {
+        let mut ctx = HookTestContext::new();
+        let mint = ctx.execute_create_hooked_mint();
+        ctx.execute_initialize_transfer_hook(&mint);
+
+        let owner = ctx.payer.pubkey();
+        let recipient = Pubkey::new_unique();
+        let source_token = ctx.execute_create_ata(&mint, &owner);
+        let destination_token = ctx.execute_create_ata(&mint, &recipient);
+        let whitelist_entry = ctx.execute_add_to_whitelist(&mint, &owner);
+
+        // Invoke the hook directly instead of through a Token-2022 transfer
+        let transfer_hook_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TransferHook {
+                source_token,
+                mint,
+                destination_token,
+                owner,
+                extra_account_meta_list: pda(&[b"extra-account-metas", mint.as_ref()]),
+                whitelist_entry,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::TransferHook { amount: 1 }.data(),
+        };
+
+        let message = Message::new(&[transfer_hook_ix], Some(&ctx.payer.pubkey()));
+        let transaction = Transaction::new(&[&ctx.payer], message, ctx.program.latest_blockhash());
+        let result = ctx.program.send_transaction(transaction);
+
+        assert_eq!(
+            result.err().unwrap().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::NotTransferring.into())
+            )
+        );
+
+        msg!("✓ Direct transfer_hook call rejected with NotTransferring");
+    }
+
+    #[test]
+    pub fn test_token_factory_sets_hook_authority() {
+        let mut ctx = HookTestContext::new();
+        let mint = Keypair::new();
+        let hook_config = pda(&[b"hook_config", mint.pubkey().as_ref()]);
+
+        let token_factory_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TokenFactory {
+                user: ctx.payer.pubkey(),
+                mint: mint.pubkey(),
+                extra_account_meta_list: pda(&[b"extra-account-metas", mint.pubkey().as_ref()]),
+                hook_config,
+                creator_whitelist_entry: None,
+                system_program: SYSTEM_PROGRAM_ID,
+                token_program: TOKEN_2022_PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::InitTokenFactory {}.data(),
+        };
+        ctx.send(&[token_factory_ix], &[&mint]);
+
+        let mint_account = ctx.program.get_account(&mint.pubkey()).unwrap();
+        let mint_state = StateWithExtensions::<SplMint>::unpack(&mint_account.data).unwrap();
+        let hook_authority =
+            Option::<Pubkey>::from(mint_state.get_extension::<TransferHook>().unwrap().authority);
+
+        let hook_config_account = ctx.program.get_account(&hook_config).unwrap();
+        let hook_config_data =
+            crate::state::HookConfig::try_deserialize(&mut hook_config_account.data.as_ref())
+                .unwrap();
+
+        // Same rule as the standalone initialize: the mint's transfer-hook authority
+        assert_eq!(hook_authority, Some(ctx.payer.pubkey()));
+        assert_eq!(Some(hook_config_data.authority), hook_authority);
+
+        msg!("✓ TokenFactory takes the hook authority from the mint");
+    }
+
+    #[test]
+    pub fn test_update_extra_account_meta_list_realloc() {
+        let mut ctx = HookTestContext::new();
+        let mint = ctx.execute_create_hooked_mint();
+        ctx.execute_initialize_transfer_hook(&mint);
+        let meta_list = pda(&[b"extra-account-metas", mint.as_ref()]);
+        let current = ctx.program.get_account(&meta_list).unwrap();
+        let metas = InitializeExtraAccountMetaList::extra_account_metas().unwrap();
+
+        // Only the mint's transfer-hook authority may rewrite the list
+        let stranger = Keypair::new();
+        ctx.program.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert_eq!(
+            ctx.try_update_extra_account_meta_list(&mint, &stranger),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::Unauthorized.into())
+            ))
+        );
+
+        let payer = ctx.payer.insecure_clone();
+
+        // A list from an older policy with fewer metas grows, topped up by the authority
+        ctx.set_extra_account_metas(&mint, &metas[..1]);
+        ctx.try_update_extra_account_meta_list(&mint, &payer).unwrap();
+        let grown = ctx.program.get_account(&meta_list).unwrap();
+        assert_eq!(grown.data, current.data);
+        assert_eq!(grown.lamports, current.lamports);
+
+        // A list with more metas shrinks and refunds the excess rent, which outweighs the fee
+        let unused_meta = ExtraAccountMeta::new_with_pubkey(&Pubkey::new_unique(), false, false);
+        let mut longer = metas.clone();
+        longer.push(unused_meta.unwrap());
+        ctx.set_extra_account_metas(&mint, &longer);
+        let payer_before = ctx.program.get_balance(&payer.pubkey()).unwrap();
+        ctx.try_update_extra_account_meta_list(&mint, &payer).unwrap();
+        let shrunk = ctx.program.get_account(&meta_list).unwrap();
+        assert_eq!(shrunk.data, current.data);
+        assert_eq!(shrunk.lamports, current.lamports);
+        assert!(ctx.program.get_balance(&payer.pubkey()).unwrap() > payer_before);
+
+        msg!("✓ Only the hook authority can update the meta list");
+        msg!("✓ Older, smaller lists grow to the current metas");
+        msg!("✓ Larger lists shrink and refund the excess rent");
+    }
+}
@@ -319,12 +319,14 @@ describe("whitelist-transfer-hook (using init_token_factory)", () => {
         ASSOCIATED_TOKEN_PROGRAM_ID
     );
 
-    it("Creates mint using init_token_factory instruction", async () => {
+    it("Creates mint, ExtraAccountMetaList and creator whitelist entry in init_token_factory", async () => {
         const sig = await program.methods
             .initTokenFactory()
             .accountsPartial({
                 user: wallet.publicKey,
                 mint: mint.publicKey,
+                extraAccountMetaList: pdaForExtraAccountMetaList(mint.publicKey),
+                creatorWhitelistEntry: pdaForWhitelistEntry(mint.publicKey, wallet.publicKey),
                 systemProgram: SystemProgram.programId,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
             })
@@ -335,22 +337,6 @@ describe("whitelist-transfer-hook (using init_token_factory)", () => {
         console.log("Mint address:", mint.publicKey.toBase58());
     });
 
-    it("Initializes ExtraAccountMetaList for the new mint", async () => {
-        const extraAccountMetaListPDA = pdaForExtraAccountMetaList(mint.publicKey);
-
-        const sig = await program.methods
-            .initializeTransferHook()
-            .accountsPartial({
-                payer: wallet.publicKey,
-                mint: mint.publicKey,
-                extraAccountMetaList: extraAccountMetaListPDA,
-                systemProgram: SystemProgram.programId,
-            })
-            .rpc({ skipPreflight: true, commitment: "confirmed" });
-
-        console.log("ExtraAccountMetaList initialized:", sig);
-    });
-
     it("Creates ATAs and mints tokens", async () => {
         const amount = 100n * 10n ** 9n;
 
@@ -387,19 +373,7 @@ describe("whitelist-transfer-hook (using init_token_factory)", () => {
         console.log("ATAs created and tokens minted:", sig);
     });
 
-    it("Adds wallet to whitelist and transfers successfully", async () => {
-        const whitelistPDA = pdaForWhitelistEntry(mint.publicKey, wallet.publicKey);
-
-        await program.methods
-            .addToWhitelist(wallet.publicKey)
-            .accountsPartial({
-                admin: wallet.publicKey,
-                mint: mint.publicKey,
-                whitelistEntry: whitelistPDA,
-                systemProgram: SystemProgram.programId,
-            })
-            .rpc({ skipPreflight: true });
-
+    it("Transfers successfully as the creator whitelisted at init", async () => {
         const amount = 1n * 10n ** 9n;
         const transferIx = await createTransferCheckedWithTransferHookInstruction(
             provider.connection,